@@ -0,0 +1,13 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Archive a habit to stop tracking it, keeping its logs.
+///
+/// Archived habits are hidden from 'habit list', 'habit today' and 'habit show'.
+/// Use 'habit unarchive' to track it again.
+pub struct ArchiveCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to archive.
+    pub habit: String,
+}
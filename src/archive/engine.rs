@@ -0,0 +1,39 @@
+use crate::archive::cli::ArchiveCli;
+use crate::db;
+use crate::engine::Engine;
+use anyhow::anyhow;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: ArchiveCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(ArchiveEngine {
+        db_path,
+        habit: cli.habit,
+    })
+}
+
+struct ArchiveEngine {
+    db_path: PathBuf,
+    habit: String,
+}
+
+impl Engine for ArchiveEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // errors if the habit does not exist
+        let habit = db::habit_get_by_name(&conn, &self.habit)?;
+        if habit.archived {
+            return Err(anyhow!("Habit '{}' is already archived!", self.habit));
+        }
+
+        db::habit_update_archived(&conn, &self.habit, true)?;
+
+        println!("Habit '{}' successfully archived!", self.habit);
+        println!(
+            "Its logs are kept. Run 'habit unarchive {}' to track it again.",
+            self.habit
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Log reps for several habits at once.
+///
+/// Pick among the habits scheduled today that have no rep logged yet.
+pub struct CheckinCli {}
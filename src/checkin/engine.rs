@@ -0,0 +1,97 @@
+use crate::config::Config;
+use crate::db;
+use crate::engine::Engine;
+use crate::habit::Habit;
+use crate::prompt;
+use anyhow::Context;
+use chrono::{Local, NaiveDate};
+use colored::Colorize;
+use rusqlite::Connection;
+use std::path::PathBuf;
+
+pub fn get_engine(db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(CheckinEngine { db_path, config })
+}
+
+struct CheckinEngine {
+    db_path: PathBuf,
+    config: Config,
+}
+
+impl Engine for CheckinEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let mut conn = db::open_db(&self.db_path)?;
+
+        // select habits scheduled today and not done yet
+        let today = Local::now().date_naive();
+        let mut habits = Vec::new();
+        for habit in db::habit_get_all(&conn)? {
+            if !habit.archived && habit.is_due_on(&today) && self.is_left(&conn, &habit, &today)? {
+                habits.push(habit);
+            }
+        }
+        habits.sort_by_key(|habit| habit.at.on(&today)[0].clone());
+
+        if habits.is_empty() {
+            println!("Nothing left to do today. Well done!");
+            return Ok(());
+        }
+
+        // ask which ones were done
+        let done = prompt::prompt_habits("Done today", &habits)?;
+        if done.is_empty() {
+            println!("Nothing done.");
+            return Ok(());
+        }
+
+        // ask how much was done for measured habits
+        let mut values = Vec::new();
+        for habit in done.iter() {
+            values.push(if habit.is_measured() {
+                prompt::prompt_amount(habit)?
+            } else {
+                1
+            });
+        }
+
+        // log a rep for each, all or nothing
+        let tx = conn.transaction()?;
+        for (habit, value) in done.iter().zip(values) {
+            db::log_insert(&tx, &habit.name, value)?;
+        }
+        tx.commit()
+            .with_context(|| "Failed to commit logged reps to database.")?;
+
+        for habit in done.iter() {
+            println!("Rep successfully logged for '{}'.", habit.name);
+        }
+        // habits may need more than one rep a day
+        let mut n_left = 0;
+        for habit in habits.iter() {
+            if self.is_left(&conn, habit, &today)? {
+                n_left += 1;
+            }
+        }
+        println!(
+            "Good job! {} of {} left for today.",
+            format!("{}", n_left).bold(),
+            habits.len()
+        );
+
+        Ok(())
+    }
+}
+
+impl CheckinEngine {
+    /// Whether `habit` is not done on `date` yet, nor enough times in its week or month
+    /// for habits to do a number of times per period.
+    fn is_left(&self, conn: &Connection, habit: &Habit, date: &NaiveDate) -> anyhow::Result<bool> {
+        let amount_per_day = db::get_all_amount_per_day_for_habit(conn, &habit.name)?;
+        let is_done = habit.is_done_on(&amount_per_day, date)
+            || habit
+                .period_progress(&amount_per_day, date, &self.config.week_start)
+                .is_some_and(|(period, n_done)| n_done >= period.n as usize);
+
+        Ok(!is_done)
+    }
+}
@@ -1,7 +1,14 @@
+use crate::profile::{self, ProfileCli};
 use crate::{
-    delete::DeleteCli, edit::EditCli, list::ListCli, log::LogCli, new::NewCli, show::ShowCli,
+    archive::ArchiveCli, checkin::CheckinCli, config::ConfigCli, delete::DeleteCli, edit::EditCli,
+    export::ExportCli, history::HistoryCli, import::ImportCli, list::ListCli, log::LogCli,
+    new::NewCli, pause::PauseCli, remind::RemindCli, resume::ResumeCli, show::ShowCli,
+    stats::StatsCli, tag::TagCli, timers::TimersCli, today::TodayCli, unarchive::UnarchiveCli,
+    undo::UndoCli, unlog::UnlogCli,
 };
+use anyhow::anyhow;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(version)]
@@ -12,16 +19,84 @@ use clap::{Parser, Subcommand};
 /// Create habits, log your reps and see your progress via
 /// cool terminal-based visualizations (yes, there is ASCII art!).
 pub struct Cli {
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, global = true, env = "HABIT_DB")]
+    /// The database file to use.
+    ///
+    /// Defaults to the one of the profile in use.
+    pub db: Option<PathBuf>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, global = true, env = "HABIT_PROFILE", conflicts_with = "db")]
+    /// The profile to use (see 'habit profile').
+    ///
+    /// Defaults to the one set with 'habit profile switch'.
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
+impl Cli {
+    /// The database file to use, given the options.
+    pub fn db_path(&self) -> anyhow::Result<PathBuf> {
+        if let Some(ref db) = self.db {
+            return Ok(db.clone());
+        }
+
+        let name = match self.profile {
+            Some(ref name) => {
+                profile::validate_profile_name(name)?;
+                if !profile::profile_exists(name) {
+                    return Err(anyhow!(
+                        "Profile '{}' does not exists! Run 'habit profile create {}' to make it.",
+                        name,
+                        name
+                    ));
+                }
+                name.clone()
+            }
+            None => {
+                let name = profile::current_profile()?;
+                if profile::validate_profile_name(&name).is_err() || !profile::profile_exists(&name)
+                {
+                    return Err(anyhow!(
+                        "Current profile '{}' does not exists! Run 'habit profile switch {}' to use the default one.",
+                        name,
+                        profile::DEFAULT_PROFILE
+                    ));
+                }
+                name
+            }
+        };
+
+        Ok(profile::profile_db_path(&name))
+    }
+}
+
 #[derive(Subcommand, Clone, Debug, PartialEq, Eq)]
 pub enum Command {
     New(NewCli),
     Edit(EditCli),
     Delete(DeleteCli),
+    Archive(ArchiveCli),
+    Unarchive(UnarchiveCli),
+    Pause(PauseCli),
+    Resume(ResumeCli),
+    Tag(TagCli),
     List(ListCli),
     Log(LogCli),
+    Checkin(CheckinCli),
+    Unlog(UnlogCli),
+    Undo(UndoCli),
+    History(HistoryCli),
     Show(ShowCli),
+    Stats(StatsCli),
+    Today(TodayCli),
+    Remind(RemindCli),
+    Schedule(TimersCli),
+    Export(ExportCli),
+    Import(ImportCli),
+    Profile(ProfileCli),
+    Config(ConfigCli),
 }
@@ -0,0 +1,7 @@
+mod cli;
+mod engine;
+mod settings;
+
+pub use cli::ConfigCli;
+pub use engine::get_engine;
+pub use settings::{Config, Notifier, Visualization, CONFIG_PATH};
@@ -0,0 +1,35 @@
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Manage settings.
+///
+/// Settings are stored in a TOML file (see 'habit config path').
+pub struct ConfigCli {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigCommand {
+    /// Show the value of a setting, or of all settings.
+    Get {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the setting to show.
+        ///
+        /// Defaults to all settings.
+        key: Option<String>,
+    },
+    /// Change the value of a setting.
+    Set {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the setting to change.
+        key: String,
+
+        #[clap(verbatim_doc_comment)]
+        /// The new value of the setting.
+        value: String,
+    },
+    /// Show the path of the config file.
+    Path,
+}
@@ -0,0 +1,58 @@
+use crate::config::cli::{ConfigCli, ConfigCommand};
+use crate::config::{Config, CONFIG_PATH};
+use crate::engine::Engine;
+use colored::Colorize;
+
+pub fn get_engine(cli: ConfigCli) -> Box<dyn Engine> {
+    Box::new(ConfigEngine {
+        command: cli.command,
+    })
+}
+
+struct ConfigEngine {
+    command: ConfigCommand,
+}
+
+impl Engine for ConfigEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        if let ConfigCommand::Path = self.command {
+            println!("{}", CONFIG_PATH.to_string_lossy());
+            return Ok(());
+        }
+
+        // Invalid settings are skipped rather than failing, so that they can be fixed.
+        let (mut config, errors) = Config::load_lenient()?;
+        // on stderr, not to mix with the values of settings
+        for error in errors {
+            eprintln!(
+                "{} Invalid config file {}, ignoring: {}",
+                "Warning:".yellow().bold(),
+                CONFIG_PATH.to_string_lossy(),
+                error
+            );
+        }
+
+        match self.command {
+            ConfigCommand::Get { key: Some(ref key) } => {
+                match config.get(key)? {
+                    toml::Value::String(value) => println!("{}", value),
+                    value => println!("{}", value),
+                }
+                Ok(())
+            }
+            ConfigCommand::Get { key: None } => {
+                for key in Config::keys() {
+                    println!("{} = {}", key, config.get(&key)?);
+                }
+                Ok(())
+            }
+            ConfigCommand::Set { ref key, ref value } => {
+                config.set(key, value)?;
+                config.save()?;
+                println!("Setting '{}' successfully updated!", key);
+                Ok(())
+            }
+            ConfigCommand::Path => unreachable!("The config path is shown above."),
+        }
+    }
+}
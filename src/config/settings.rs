@@ -0,0 +1,278 @@
+use crate::habit::{At, Day};
+use crate::CONFIG_DIR;
+use anyhow::{anyhow, Context};
+use chrono::{NaiveDate, NaiveTime};
+use lazy_static::lazy_static;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+lazy_static! {
+    pub static ref CONFIG_PATH: PathBuf = CONFIG_DIR.join("config.toml");
+}
+
+/// User settings, read from the TOML file at CONFIG_PATH.
+/// Missing settings take their default value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The day weeks start on, in the heatmap and 'habit today --week'.
+    pub week_start: Day,
+    /// The color of highlighted elements in 'habit show'.
+    pub primary_color: String,
+    /// The color of days a habit was performed on in the heatmap.
+    pub done_color: String,
+    /// The color of days a habit was missed on in the heatmap.
+    pub missed_color: String,
+    /// The visualization shown first by 'habit show'.
+    pub default_visualization: Visualization,
+    /// The format of dates (see https://docs.rs/chrono/latest/chrono/format/strftime).
+    pub date_format: String,
+    /// Whether 'habit list' is verbose by default.
+    pub list_verbose: bool,
+    /// How 'habit remind' sends notifications.
+    pub notifier: Notifier,
+    /// The script run by the script notifier, given the name of the habit,
+    /// its time and its description as arguments.
+    pub notifier_script: String,
+    /// The hours during which 'habit remind' sends no notifications,
+    /// as 'hh:mm-hh:mm' (e.g. '22:00-07:00'), or empty for none.
+    pub quiet_hours: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Visualization {
+    Heatmap,
+    BowlOfMarbles,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Notifier {
+    /// Print reminders to the standard output.
+    Stdout,
+    /// Show reminders as desktop notifications with the notify-send command.
+    NotifySend,
+    /// Run the script set in the notifier_script setting.
+    Script,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            week_start: Day::Monday,
+            primary_color: String::from("lightblue"),
+            done_color: String::from("green"),
+            missed_color: String::from("red"),
+            default_visualization: Visualization::Heatmap,
+            date_format: String::from("%Y-%m-%d"),
+            list_verbose: false,
+            notifier: Notifier::Stdout,
+            notifier_script: String::new(),
+            quiet_hours: String::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Read the config file, or get the default config if there is none.
+    pub fn load() -> anyhow::Result<Self> {
+        if !CONFIG_PATH.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = fs::read_to_string(CONFIG_PATH.as_path()).with_context(|| {
+            format!(
+                "Failed to read config file {}.",
+                CONFIG_PATH.to_string_lossy()
+            )
+        })?;
+        let config: Config = toml::from_str(&content)
+            .with_context(|| format!("Invalid config file {}.", CONFIG_PATH.to_string_lossy()))?;
+        config
+            .validate()
+            .with_context(|| format!("Invalid config file {}.", CONFIG_PATH.to_string_lossy()))?;
+
+        Ok(config)
+    }
+
+    /// Read the config file like `load`, but skip the settings that are invalid
+    /// instead of failing, returning the errors about those along with the config.
+    pub fn load_lenient() -> anyhow::Result<(Self, Vec<anyhow::Error>)> {
+        let mut config = Config::default();
+        if !CONFIG_PATH.exists() {
+            return Ok((config, Vec::new()));
+        }
+
+        let content = fs::read_to_string(CONFIG_PATH.as_path()).with_context(|| {
+            format!(
+                "Failed to read config file {}.",
+                CONFIG_PATH.to_string_lossy()
+            )
+        })?;
+        let table: toml::Table = match toml::from_str(&content) {
+            Ok(table) => table,
+            Err(e) => return Ok((config, vec![anyhow!(e)])),
+        };
+        let mut errors = Vec::new();
+        for (key, value) in table {
+            if let Err(e) = config.set_value(&key, value) {
+                errors.push(e);
+            }
+        }
+
+        Ok((config, errors))
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        fs::create_dir_all(CONFIG_DIR.as_path())?;
+        fs::write(CONFIG_PATH.as_path(), toml::to_string(self)?).with_context(|| {
+            format!(
+                "Failed to write config file {}.",
+                CONFIG_PATH.to_string_lossy()
+            )
+        })
+    }
+
+    /// Check the settings that serde cannot check by itself.
+    fn validate(&self) -> anyhow::Result<()> {
+        for (key, color) in [
+            ("primary_color", &self.primary_color),
+            ("done_color", &self.done_color),
+            ("missed_color", &self.missed_color),
+        ] {
+            Color::from_str(color).map_err(|_| {
+                anyhow!(
+                    "Invalid {} '{}'. Use a color name like 'lightblue', or a hex code like '#ff0000'.",
+                    key,
+                    color
+                )
+            })?;
+        }
+        // formats with time fields parse fine but fail to format dates
+        let mut sample = String::new();
+        if write!(sample, "{}", NaiveDate::default().format(&self.date_format)).is_err() {
+            return Err(anyhow!(
+                "Invalid date_format '{}'. Use only date fields, like '%Y-%m-%d'.",
+                self.date_format
+            ));
+        }
+        if !self.quiet_hours.is_empty() && parse_quiet_hours(&self.quiet_hours).is_none() {
+            return Err(anyhow!(
+                "Invalid quiet_hours '{}'. Use 'hh:mm-hh:mm', e.g. '22:00-07:00'.",
+                self.quiet_hours
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The names of the settings, as in the config file.
+    pub fn keys() -> Vec<String> {
+        Self::default().to_table().keys().cloned().collect()
+    }
+
+    /// Get the value of setting `key`, as in the config file.
+    pub fn get(&self, key: &str) -> anyhow::Result<toml::Value> {
+        self.to_table()
+            .remove(key)
+            .ok_or_else(|| anyhow!("Unknown setting '{}'.", key))
+    }
+
+    /// Set setting `key` to `value`, given as it would be in the config file
+    /// but without quotes for strings.
+    pub fn set(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+        let new_value = match self.to_table().get(key) {
+            Some(toml::Value::Boolean(_)) => toml::Value::Boolean(
+                bool::from_str(value)
+                    .map_err(|_| anyhow!("Invalid {} '{}'. Use true or false.", key, value))?,
+            ),
+            Some(_) => toml::Value::String(value.to_string()),
+            None => return Err(anyhow!("Unknown setting '{}'.", key)),
+        };
+
+        self.set_value(key, new_value)
+    }
+
+    /// Set setting `key` to `value`, as it would be in the config file.
+    fn set_value(&mut self, key: &str, value: toml::Value) -> anyhow::Result<()> {
+        let mut table = self.to_table();
+        if !table.contains_key(key) {
+            return Err(anyhow!("Unknown setting '{}'.", key));
+        }
+        let display_value = match value {
+            toml::Value::String(ref s) => s.clone(),
+            ref value => value.to_string(),
+        };
+        table.insert(key.to_string(), value);
+
+        let config: Config =
+            toml::Value::Table(table)
+                .try_into()
+                .map_err(|e: toml::de::Error| {
+                    anyhow!("Invalid {} '{}'. {}", key, display_value, e.message())
+                })?;
+        config.validate()?;
+        *self = config;
+
+        Ok(())
+    }
+
+    fn to_table(&self) -> toml::Table {
+        toml::Table::try_from(self).expect("Config should always be serializable.")
+    }
+
+    pub fn primary_color(&self) -> Color {
+        Color::from_str(&self.primary_color).expect("Colors are validated when loading.")
+    }
+
+    pub fn done_color(&self) -> Color {
+        Color::from_str(&self.done_color).expect("Colors are validated when loading.")
+    }
+
+    pub fn missed_color(&self) -> Color {
+        Color::from_str(&self.missed_color).expect("Colors are validated when loading.")
+    }
+
+    /// Whether `time` is during the quiet hours, which can go past midnight.
+    pub fn is_quiet_at(&self, time: NaiveTime) -> bool {
+        let Some((start, end)) = parse_quiet_hours(&self.quiet_hours) else {
+            return false;
+        };
+        if start.time() <= end.time() {
+            start.time() <= time && time < end.time()
+        } else {
+            start.time() <= time || time < end.time()
+        }
+    }
+}
+
+/// Parse quiet hours given as 'hh:mm-hh:mm' into their start and end.
+fn parse_quiet_hours(s: &str) -> Option<(At, At)> {
+    let (start, end) = s.split_once('-')?;
+    Some((
+        At::from_str(start.trim()).ok()?,
+        At::from_str(end.trim()).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn date_format_with_time_fields_is_invalid() {
+        let mut config = Config::default();
+        for format in ["%d/%m/%Y", "%A %e %B", "%F"] {
+            assert!(config.set("date_format", format).is_ok(), "{}", format);
+        }
+        for format in ["%Y-%m-%d %H:%M", "%S", "%z", "%Q"] {
+            assert!(config.set("date_format", format).is_err(), "{}", format);
+        }
+        assert_eq!(config.date_format, "%F");
+    }
+}
@@ -1,53 +1,147 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::habit::{At, Day, Habit};
-use crate::DB_PATH;
+use crate::habit::{Habit, Log, Pause};
+use crate::schedule::Schedule;
+use crate::times::Times;
 use anyhow::anyhow;
 use anyhow::Context;
+use chrono::NaiveDate;
 use rusqlite::Connection;
+use std::path::Path;
 
-pub fn open_db() -> anyhow::Result<Connection> {
-    Connection::open(DB_PATH.clone()).with_context(|| {
+pub fn open_db(db_path: &Path) -> anyhow::Result<Connection> {
+    Connection::open(db_path).with_context(|| {
         format!(
             "Failed to open sqlite db file at location {}",
-            DB_PATH.to_string_lossy()
+            db_path.to_string_lossy()
         )
     })
 }
 
-pub fn habit_create_table(conn: &Connection) -> anyhow::Result<()> {
-    conn.execute(
-        "CREATE TABLE habit (
-            name        TEXT PRIMARY KEY,
-            description TEXT NOT NULL,
-            days        TEXT NOT NULL,
-            hour        INTEGER NOT NULL,
-            minutes     INTEGER NOT NULL
-        )",
-        (),
-    )
-    .with_context(|| "Failed to create habit table.")?;
+/// Migrations of the database schema, applied in order.
+/// The schema version of a database is the number of migrations applied to it,
+/// and is stored in its `user_version` pragma.
+/// Never modify a migration that has been released, add a new one instead.
+const MIGRATIONS: [&str; 9] = [
+    // 1: Initial schema.
+    // Databases made before migrations existed have version 0 but already have
+    // the tables, hence the IF NOT EXISTS.
+    "CREATE TABLE IF NOT EXISTS habit (
+        name        TEXT PRIMARY KEY,
+        description TEXT NOT NULL,
+        days        TEXT NOT NULL,
+        hour        INTEGER NOT NULL,
+        minutes     INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS log (
+        created   INTEGER PRIMARY KEY,
+        habit     TEXT NOT NULL REFERENCES habit(name) ON DELETE CASCADE
+    );",
+    // 2: Give logs their own id, so that several reps can be logged in the same second.
+    "CREATE TABLE log_new (
+        id        INTEGER PRIMARY KEY,
+        created   INTEGER NOT NULL,
+        habit     TEXT NOT NULL REFERENCES habit(name) ON DELETE CASCADE
+    );
+    INSERT INTO log_new (created, habit) SELECT created, habit FROM log ORDER BY created;
+    DROP TABLE log;
+    ALTER TABLE log_new RENAME TO log;
+    CREATE INDEX log_created ON log(created);
+    CREATE INDEX log_habit_created ON log(habit, created);",
+    // 3: Measured habits, whose reps have an amount.
+    "ALTER TABLE habit ADD COLUMN unit TEXT;
+    ALTER TABLE habit ADD COLUMN target INTEGER;
+    ALTER TABLE log ADD COLUMN value INTEGER NOT NULL DEFAULT 1;",
+    // 4: Schedules other than days of the week.
+    // Days were separated by spaces, schedules of days by commas.
+    "ALTER TABLE habit ADD COLUMN schedule TEXT NOT NULL DEFAULT '';
+    UPDATE habit SET schedule = replace(days, ' ', ',');
+    ALTER TABLE habit DROP COLUMN days;",
+    // 5: Several times per habit, stored like they are input ('08:00,20:00').
+    "ALTER TABLE habit ADD COLUMN at TEXT NOT NULL DEFAULT '';
+    UPDATE habit SET at = printf('%02d:%02d', hour, minutes);
+    ALTER TABLE habit DROP COLUMN hour;
+    ALTER TABLE habit DROP COLUMN minutes;",
+    // 6: Snoozed reminders, until a unix timestamp.
+    "CREATE TABLE snooze (
+        habit     TEXT PRIMARY KEY REFERENCES habit(name) ON DELETE CASCADE,
+        until     INTEGER NOT NULL
+    );",
+    // 7: Archived and paused habits.
+    // Pauses are stored like they are displayed, separated by commas.
+    "ALTER TABLE habit ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE habit ADD COLUMN pauses TEXT NOT NULL DEFAULT '';",
+    // 8: Tags of habits.
+    "CREATE TABLE tag (
+        habit     TEXT NOT NULL REFERENCES habit(name) ON DELETE CASCADE,
+        name      TEXT NOT NULL,
+        PRIMARY KEY (habit, name)
+    );",
+    // 9: Notes and ratings of logged reps.
+    "ALTER TABLE log ADD COLUMN note TEXT;
+    ALTER TABLE log ADD COLUMN rating INTEGER;",
+];
+
+/// The columns of the habit table selected to make habits (see habit_from_row),
+/// the tags of a habit being joined with commas.
+const HABIT_COLUMNS: &str = "name, description, schedule, at, unit, target, archived, pauses,
+    (SELECT group_concat(tag.name, ',') FROM tag WHERE tag.habit = habit.name)";
+
+/// The schema version of databases made by this version of habit.
+pub const SCHEMA_VERSION: usize = MIGRATIONS.len();
+
+/// Bring the database schema up to date by applying the migrations it misses,
+/// all in one transaction.
+/// Fails if the database was made by a more recent version of habit.
+pub fn migrate(conn: &mut Connection) -> anyhow::Result<()> {
+    let version = conn
+        .query_row("PRAGMA user_version", (), |row| row.get::<usize, usize>(0))
+        .with_context(|| "Failed to get the schema version of the database.")?;
+
+    if version > SCHEMA_VERSION {
+        return Err(anyhow!(
+            "The database has schema version {}, but this version of habit only supports up to {}. Consider upgrading habit.",
+            version,
+            SCHEMA_VERSION
+        ));
+    }
+    if version == SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tx = conn.transaction()?;
+    for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        tx.execute_batch(migration)
+            .with_context(|| format!("Failed to migrate the database to version {}.", i + 1))?;
+    }
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)
+        .with_context(|| "Failed to update the schema version of the database.")?;
+    tx.commit()
+        .with_context(|| "Failed to commit the database migrations.")?;
 
     Ok(())
 }
 
 pub fn habit_insert(conn: &Connection, habit: &Habit) -> anyhow::Result<()> {
     conn.execute(
-        "INSERT INTO habit (name, description, days, hour, minutes) VALUES (?1, ?2, ?3, ?4, ?5)",
+        "INSERT INTO habit (name, description, schedule, at, unit, target, archived, pauses)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
         rusqlite::params![
             habit.name,
             habit.description,
-            habit
-                .days
-                .iter()
-                .map(|d| d.to_string())
-                .collect::<Vec<String>>()
-                .join(" "),
-            habit.at.hour,
-            habit.at.minutes,
+            habit.schedule.to_string(),
+            habit.at.to_string(),
+            habit.unit,
+            habit.target,
+            habit.archived,
+            pauses_to_string(&habit.pauses),
         ],
     )
     .with_context(|| "Failed to insert habit into database.")?;
+    for tag in habit.tags.iter() {
+        tag_insert(conn, &habit.name, tag)?;
+    }
 
     Ok(())
 }
@@ -57,11 +151,25 @@ pub fn habit_update_name(
     habit_name: &str,
     new_name: &str,
 ) -> anyhow::Result<()> {
-    conn.execute(
+    // Foreign keys do not cascade updates, so the rows referring to the habit
+    // are updated too, all at once, checking foreign keys only when done.
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("PRAGMA defer_foreign_keys = ON;", ())?;
+    for query in [
         "UPDATE habit SET name = ?1 WHERE name = ?2",
-        rusqlite::params![new_name, habit_name],
-    )
-    .with_context(|| {
+        "UPDATE log SET habit = ?1 WHERE habit = ?2",
+        "UPDATE tag SET habit = ?1 WHERE habit = ?2",
+        "UPDATE snooze SET habit = ?1 WHERE habit = ?2",
+    ] {
+        tx.execute(query, rusqlite::params![new_name, habit_name])
+            .with_context(|| {
+                format!(
+                    "Failed to update name of habit '{}' to '{}'.",
+                    habit_name, new_name
+                )
+            })?;
+    }
+    tx.commit().with_context(|| {
         format!(
             "Failed to update name of habit '{}' to '{}'.",
             habit_name, new_name
@@ -90,35 +198,29 @@ pub fn habit_update_description(
     Ok(())
 }
 
-pub fn habit_update_days(
+pub fn habit_update_schedule(
     conn: &Connection,
     habit_name: &str,
-    new_days: &[Day],
+    new_schedule: &Schedule,
 ) -> anyhow::Result<()> {
-    let new_days_str = new_days
-        .iter()
-        .map(|d| d.to_string())
-        .collect::<Vec<String>>()
-        .join(" ");
-
     conn.execute(
-        "UPDATE habit SET days = ?1 WHERE name = ?2",
-        rusqlite::params![new_days_str, habit_name],
+        "UPDATE habit SET schedule = ?1 WHERE name = ?2",
+        rusqlite::params![new_schedule.to_string(), habit_name],
     )
     .with_context(|| {
         format!(
-            "Failed to update days of habit '{}' to '{}'.",
-            habit_name, new_days_str
+            "Failed to update schedule of habit '{}' to '{}'.",
+            habit_name, new_schedule
         )
     })?;
 
     Ok(())
 }
 
-pub fn habit_update_at(conn: &Connection, habit_name: &str, new_at: &At) -> anyhow::Result<()> {
+pub fn habit_update_at(conn: &Connection, habit_name: &str, new_at: &Times) -> anyhow::Result<()> {
     conn.execute(
-        "UPDATE habit SET hour = ?1, minutes = ?2 WHERE name = ?3",
-        rusqlite::params![new_at.hour, new_at.minutes, habit_name],
+        "UPDATE habit SET at = ?1 WHERE name = ?2",
+        rusqlite::params![new_at.to_string(), habit_name],
     )
     .with_context(|| {
         format!(
@@ -130,6 +232,111 @@ pub fn habit_update_at(conn: &Connection, habit_name: &str, new_at: &At) -> anyh
     Ok(())
 }
 
+pub fn habit_update_unit(
+    conn: &Connection,
+    habit_name: &str,
+    new_unit: Option<&str>,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE habit SET unit = ?1 WHERE name = ?2",
+        rusqlite::params![new_unit, habit_name],
+    )
+    .with_context(|| format!("Failed to update unit of habit '{}'.", habit_name))?;
+
+    Ok(())
+}
+
+pub fn habit_update_target(
+    conn: &Connection,
+    habit_name: &str,
+    new_target: Option<u32>,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE habit SET target = ?1 WHERE name = ?2",
+        rusqlite::params![new_target, habit_name],
+    )
+    .with_context(|| format!("Failed to update target of habit '{}'.", habit_name))?;
+
+    Ok(())
+}
+
+pub fn habit_update_archived(
+    conn: &Connection,
+    habit_name: &str,
+    archived: bool,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE habit SET archived = ?1 WHERE name = ?2",
+        rusqlite::params![archived, habit_name],
+    )
+    .with_context(|| format!("Failed to update archived of habit '{}'.", habit_name))?;
+
+    Ok(())
+}
+
+pub fn habit_update_pauses(
+    conn: &Connection,
+    habit_name: &str,
+    pauses: &[Pause],
+) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE habit SET pauses = ?1 WHERE name = ?2",
+        rusqlite::params![pauses_to_string(pauses), habit_name],
+    )
+    .with_context(|| format!("Failed to update pauses of habit '{}'.", habit_name))?;
+
+    Ok(())
+}
+
+fn pauses_to_string(pauses: &[Pause]) -> String {
+    pauses
+        .iter()
+        .map(|pause| pause.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Tag `habit` with `tag`. Returns whether it was not already tagged with it.
+pub fn tag_insert(conn: &Connection, habit: &str, tag: &str) -> anyhow::Result<bool> {
+    let n_inserted = conn
+        .execute(
+            "INSERT OR IGNORE INTO tag (habit, name) VALUES (?1, ?2)",
+            rusqlite::params![habit, tag],
+        )
+        .with_context(|| format!("Failed to add tag '{}' to habit '{}'.", tag, habit))?;
+
+    Ok(n_inserted > 0)
+}
+
+/// Remove `tag` from `habit`. Returns whether it was tagged with it.
+pub fn tag_delete(conn: &Connection, habit: &str, tag: &str) -> anyhow::Result<bool> {
+    let n_deleted = conn
+        .execute(
+            "DELETE FROM tag WHERE habit = ?1 AND name = ?2",
+            rusqlite::params![habit, tag],
+        )
+        .with_context(|| format!("Failed to remove tag '{}' from habit '{}'.", tag, habit))?;
+
+    Ok(n_deleted > 0)
+}
+
+/// All tags in use, in order, each with the number of habits tagged with it.
+pub fn tag_get_all(conn: &Connection) -> anyhow::Result<Vec<(String, usize)>> {
+    let mut stmt = conn
+        .prepare("SELECT name, COUNT(*) FROM tag GROUP BY name ORDER BY name")
+        .with_context(|| "Failed to prepare 'select tags' statement.")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<usize, String>(0)?, row.get::<usize, usize>(1)?))
+    })?;
+
+    let mut tags = Vec::new();
+    for row in rows {
+        tags.push(row?);
+    }
+
+    Ok(tags)
+}
+
 pub fn habit_exists(conn: &Connection, habit_name: &str) -> anyhow::Result<bool> {
     match conn.query_row(
         "SELECT name FROM habit WHERE name = ?1",
@@ -148,23 +355,9 @@ pub fn habit_exists(conn: &Connection, habit_name: &str) -> anyhow::Result<bool>
 
 pub fn habit_get_by_name(conn: &Connection, habit_name: &str) -> anyhow::Result<Habit> {
     let query_res = conn.query_row(
-        "SELECT name, description, days, hour, minutes FROM habit WHERE name = ?1",
+        &format!("SELECT {} FROM habit WHERE name = ?1", HABIT_COLUMNS),
         rusqlite::params![habit_name],
-        |row| {
-            let days: Vec<Day> = row
-                .get::<usize, String>(2)?
-                .split(' ')
-                .map(|d_str| Day::from_str(d_str).expect("Days from database should be valid."))
-                .collect();
-            let at = At::build(row.get::<usize, u8>(3)?, row.get::<usize, u8>(4)?)
-                .expect("Hour and minutes from database should be valid.");
-            Ok(Habit::new(
-                row.get::<usize, String>(0)?,
-                row.get::<usize, String>(1)?,
-                days,
-                at,
-            ))
-        },
+        habit_from_row,
     );
 
     match query_res {
@@ -183,8 +376,7 @@ pub fn habit_get_by_name(conn: &Connection, habit_name: &str) -> anyhow::Result<
 pub fn habit_get_with_most_recent_log(conn: &Connection) -> anyhow::Result<Habit> {
     let habit_name = conn
         .query_row(
-            "SELECT habit FROM log ORDER BY created DESC LIMIT 1;
-",
+            "SELECT habit FROM log ORDER BY created DESC, id DESC LIMIT 1;",
             (),
             |row| row.get::<usize, String>(0),
         )
@@ -195,25 +387,11 @@ pub fn habit_get_with_most_recent_log(conn: &Connection) -> anyhow::Result<Habit
 
 pub fn habit_get_all(conn: &Connection) -> anyhow::Result<Vec<Habit>> {
     let mut stmt = conn
-        .prepare("SELECT name, description, days, hour, minutes FROM habit")
+        .prepare(&format!("SELECT {} FROM habit", HABIT_COLUMNS))
         .with_context(|| "Failed to prepare 'select all habits' statement.")?;
 
     let rows = stmt
-        .query_map([], |row| {
-            let days: Vec<Day> = row
-                .get::<usize, String>(2)?
-                .split(' ')
-                .map(|d_str| Day::from_str(d_str).expect("Days from database should be valid."))
-                .collect();
-            let at = At::build(row.get::<usize, u8>(3)?, row.get::<usize, u8>(4)?)
-                .expect("Hour and minutes from database should be valid.");
-            Ok(Habit::new(
-                row.get::<usize, String>(0)?,
-                row.get::<usize, String>(1)?,
-                days,
-                at,
-            ))
-        })
+        .query_map([], habit_from_row)
         .with_context(|| "Failed to select all habits.")?;
 
     let mut habits = Vec::new();
@@ -224,29 +402,181 @@ pub fn habit_get_all(conn: &Connection) -> anyhow::Result<Vec<Habit>> {
     Ok(habits)
 }
 
-pub fn log_create_table(conn: &Connection) -> anyhow::Result<()> {
-    conn.execute(
-        "CREATE TABLE log (
-            created   INTEGER PRIMARY KEY,
-            habit     TEXT NOT NULL REFERENCES habit(name) ON DELETE CASCADE
-        )",
-        (),
-    )
-    .with_context(|| "Failed to create log table.")?;
+/// Make a habit out of a row holding the HABIT_COLUMNS.
+fn habit_from_row(row: &rusqlite::Row) -> rusqlite::Result<Habit> {
+    let schedule = Schedule::from_str(&row.get::<usize, String>(2)?)
+        .expect("Schedule from database should be valid.");
+    let at = Times::from_str(&row.get::<usize, String>(3)?)
+        .expect("Times from database should be valid.");
 
-    Ok(())
+    Ok(Habit {
+        unit: row.get::<usize, Option<String>>(4)?,
+        target: row.get::<usize, Option<u32>>(5)?,
+        archived: row.get::<usize, bool>(6)?,
+        pauses: row
+            .get::<usize, String>(7)?
+            .split(',')
+            .filter(|pause| !pause.is_empty())
+            .map(|pause| Pause::from_str(pause).expect("Pauses from database should be valid."))
+            .collect(),
+        tags: {
+            let mut tags: Vec<String> = row
+                .get::<usize, Option<String>>(8)?
+                .map(|tags| tags.split(',').map(|tag| tag.to_string()).collect())
+                .unwrap_or_default();
+            tags.sort();
+            tags
+        },
+        ..Habit::new(
+            row.get::<usize, String>(0)?,
+            row.get::<usize, String>(1)?,
+            schedule,
+            at,
+        )
+    })
+}
+
+/// Log a rep of `value` for `habit` now, and return the id of the new log.
+pub fn log_insert(conn: &Connection, habit: &str, value: u32) -> anyhow::Result<i64> {
+    log_insert_at(
+        conn,
+        habit,
+        chrono::Local::now().timestamp(),
+        value,
+        None,
+        None,
+    )
 }
 
-pub fn log_insert(conn: &Connection, habit: &str) -> anyhow::Result<()> {
+/// Log a rep of `value` for `habit` at the unix timestamp `created`,
+/// with an optional `note` and `rating`, and return the id of the new log.
+pub fn log_insert_at(
+    conn: &Connection,
+    habit: &str,
+    created: i64,
+    value: u32,
+    note: Option<&str>,
+    rating: Option<u8>,
+) -> anyhow::Result<i64> {
     conn.execute(
-        "INSERT INTO log (created, habit) VALUES (?1, ?2);",
-        rusqlite::params![chrono::Local::now().timestamp(), habit],
+        "INSERT INTO log (created, habit, value, note, rating) VALUES (?1, ?2, ?3, ?4, ?5);",
+        rusqlite::params![created, habit, value, note, rating],
     )
     .with_context(|| "Failed to insert log into database.")?;
 
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn log_get_by_id(conn: &Connection, id: i64) -> anyhow::Result<Log> {
+    let query_res = conn.query_row(
+        "SELECT id, habit, created, value, note, rating FROM log WHERE id = ?1",
+        rusqlite::params![id],
+        log_from_row,
+    );
+
+    match query_res {
+        Ok(log) => Ok(log),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Err(anyhow!("Log {} does not exists!", id)),
+        Err(e) => Err(anyhow!("Query to select log {} failed.\n{}", id, e)),
+    }
+}
+
+pub fn log_delete(conn: &Connection, id: i64) -> anyhow::Result<()> {
+    conn.execute("DELETE FROM log WHERE id = ?1", rusqlite::params![id])
+        .with_context(|| format!("Failed to delete log {} from database.", id))?;
+
     Ok(())
 }
 
+/// Get the last logged rep, whatever its date, for `habit` or for any habit.
+pub fn log_get_last_inserted(
+    conn: &Connection,
+    habit: Option<&str>,
+) -> anyhow::Result<Option<Log>> {
+    Ok(get_logs(
+        conn,
+        "SELECT id, habit, created, value, note, rating FROM log
+        WHERE ?1 IS NULL OR habit = ?1 ORDER BY id DESC LIMIT 1",
+        rusqlite::params![habit],
+    )
+    .with_context(|| "Failed to select last logged rep.")?
+    .pop())
+}
+
+/// Get the `n` most recent logged reps for `habit`, from newest to oldest.
+pub fn get_n_most_recent_logs_for_habit(
+    conn: &Connection,
+    habit: &str,
+    n: usize,
+) -> anyhow::Result<Vec<Log>> {
+    get_logs(
+        conn,
+        "SELECT id, habit, created, value, note, rating FROM log WHERE habit = ?1
+        ORDER BY created DESC, id DESC LIMIT ?2",
+        rusqlite::params![habit, n],
+    )
+    .with_context(|| {
+        format!(
+            "Failed to select most recent logged reps for habit '{}'.",
+            habit
+        )
+    })
+}
+
+/// Get all logged reps for `habit`, from oldest to newest.
+pub fn get_logs_for_habit(conn: &Connection, habit: &str) -> anyhow::Result<Vec<Log>> {
+    get_logs(
+        conn,
+        "SELECT id, habit, created, value, note, rating FROM log WHERE habit = ?1 ORDER BY created, id",
+        rusqlite::params![habit],
+    )
+    .with_context(|| format!("Failed to select logged reps for habit '{}'.", habit))
+}
+
+/// Get all logged reps, for any habit, from oldest to newest.
+pub fn get_all_logs(conn: &Connection) -> anyhow::Result<Vec<Log>> {
+    get_logs(
+        conn,
+        "SELECT id, habit, created, value, note, rating FROM log ORDER BY created, id",
+        rusqlite::params![],
+    )
+    .with_context(|| "Failed to select all logged reps.")
+}
+
+/// Run `query` which must select the id, habit, created, value, note and rating columns of logs.
+fn get_logs(
+    conn: &Connection,
+    query: &str,
+    params: impl rusqlite::Params,
+) -> anyhow::Result<Vec<Log>> {
+    let mut stmt = conn
+        .prepare(query)
+        .with_context(|| "Failed to prepare 'select logs' statement.")?;
+
+    let rows = stmt.query_map(params, log_from_row)?;
+
+    let mut logs = Vec::new();
+    for row in rows {
+        logs.push(row?);
+    }
+
+    Ok(logs)
+}
+
+/// Make a log out of a row holding its id, habit, created, value, note and rating columns.
+fn log_from_row(row: &rusqlite::Row) -> rusqlite::Result<Log> {
+    Ok(Log {
+        note: row.get::<usize, Option<String>>(4)?,
+        rating: row.get::<usize, Option<u8>>(5)?,
+        ..Log::new(
+            row.get::<usize, i64>(0)?,
+            row.get::<usize, String>(1)?,
+            row.get::<usize, i64>(2)?,
+            row.get::<usize, u32>(3)?,
+        )
+    })
+}
+
 pub fn get_n_logs_for_habit(conn: &Connection, habit: &str) -> anyhow::Result<usize> {
     conn.query_row(
         "SELECT COUNT(*) FROM log WHERE habit = ?1",
@@ -260,3 +590,204 @@ pub fn get_n_logs_for_habit(conn: &Connection, habit: &str) -> anyhow::Result<us
         )
     })
 }
+
+/// Sum the amounts logged for `habit` for each day, in local time.
+/// Reps of habits that are not measured have an amount of 1,
+/// so for those this is the number of reps per day.
+/// Days without any rep are absent from the returned map.
+pub fn get_all_amount_per_day_for_habit(
+    conn: &Connection,
+    habit: &str,
+) -> anyhow::Result<HashMap<NaiveDate, usize>> {
+    get_amount_per_day_for_habit_between(conn, habit, i64::MIN, i64::MAX).with_context(|| {
+        format!(
+            "Failed to sum logged amounts per day for habit '{}'.",
+            habit
+        )
+    })
+}
+
+/// Sum the amounts logged for `habit` for each day, in local time,
+/// between unix timestamps `start` (included) and `end` (excluded).
+fn get_amount_per_day_for_habit_between(
+    conn: &Connection,
+    habit: &str,
+    start: i64,
+    end: i64,
+) -> anyhow::Result<HashMap<NaiveDate, usize>> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT date(created, 'unixepoch', 'localtime') AS day, SUM(value) FROM log
+            WHERE habit = ?1 AND created >= ?2 AND created < ?3
+            GROUP BY day",
+        )
+        .with_context(|| "Failed to prepare 'sum amounts per day' statement.")?;
+
+    let rows = stmt.query_map(rusqlite::params![habit, start, end], |row| {
+        Ok((row.get::<usize, String>(0)?, row.get::<usize, usize>(1)?))
+    })?;
+
+    let mut amount_per_day = HashMap::new();
+    for row in rows {
+        let (day, amount) = row?;
+        let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d")
+            .expect("Dates computed by sqlite should be valid.");
+        amount_per_day.insert(day, amount);
+    }
+
+    Ok(amount_per_day)
+}
+
+/// Snooze the reminders of `habit` until unix timestamp `until`.
+pub fn snooze_set(conn: &Connection, habit: &str, until: i64) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT INTO snooze (habit, until) VALUES (?1, ?2)
+        ON CONFLICT (habit) DO UPDATE SET until = ?2",
+        rusqlite::params![habit, until],
+    )
+    .with_context(|| format!("Failed to snooze reminders of habit '{}'.", habit))?;
+
+    Ok(())
+}
+
+/// The unix timestamp the reminders of `habit` were last snoozed until, if ever.
+pub fn snooze_get(conn: &Connection, habit: &str) -> anyhow::Result<Option<i64>> {
+    match conn.query_row(
+        "SELECT until FROM snooze WHERE habit = ?1",
+        rusqlite::params![habit],
+        |row| row.get::<usize, i64>(0),
+    ) {
+        Ok(until) => Ok(Some(until)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(anyhow!(
+            "Query to select snooze of habit '{}' failed.\n{}",
+            habit,
+            e
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_version(conn: &Connection) -> usize {
+        conn.query_row("PRAGMA user_version", (), |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn migrate_fresh_db() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), SCHEMA_VERSION);
+        assert!(habit_get_all(&conn).unwrap().is_empty());
+
+        // migrating again does nothing
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn migrate_baseline_db() {
+        // the schema and rows of a database made before migrations existed
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE habit (
+                name        TEXT PRIMARY KEY,
+                description TEXT NOT NULL,
+                days        TEXT NOT NULL,
+                hour        INTEGER NOT NULL,
+                minutes     INTEGER NOT NULL
+            );
+            CREATE TABLE log (
+                created   INTEGER PRIMARY KEY,
+                habit     TEXT NOT NULL REFERENCES habit(name) ON DELETE CASCADE
+            );
+            INSERT INTO habit VALUES ('run', 'Go for a run', 'Monday Wednesday Friday', 7, 30);
+            INSERT INTO log VALUES (1700000000, 'run');
+            INSERT INTO log VALUES (1700100000, 'run');",
+        )
+        .unwrap();
+        assert_eq!(user_version(&conn), 0);
+
+        migrate(&mut conn).unwrap();
+        assert_eq!(user_version(&conn), SCHEMA_VERSION);
+
+        let habit = habit_get_by_name(&conn, "run").unwrap();
+        assert_eq!(habit.description, "Go for a run");
+        assert_eq!(
+            habit.schedule,
+            Schedule::from_str("Monday,Wednesday,Friday").unwrap()
+        );
+        assert_eq!(habit.at, Times::from_str("07:30").unwrap());
+        assert_eq!(habit.unit, None);
+        assert!(!habit.archived);
+        assert!(habit.tags.is_empty());
+
+        let logs = get_logs_for_habit(&conn, "run").unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.created).collect::<Vec<i64>>(),
+            vec![1700000000, 1700100000]
+        );
+        assert!(logs
+            .iter()
+            .all(|log| log.value == 1 && log.note.is_none() && log.rating.is_none()));
+    }
+
+    #[test]
+    fn last_inserted_log_is_not_the_latest_dated() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+        for name in ["run", "read"] {
+            habit_insert(
+                &conn,
+                &Habit::new(
+                    String::from(name),
+                    String::new(),
+                    Schedule::from_str("daily").unwrap(),
+                    Times::from_str("08:00").unwrap(),
+                ),
+            )
+            .unwrap();
+        }
+        assert!(log_get_last_inserted(&conn, None).unwrap().is_none());
+
+        log_insert_at(&conn, "run", 1700000000, 1, None, None).unwrap();
+        log_insert_at(&conn, "read", 1700100000, 1, None, None).unwrap();
+        let backdated = log_insert_at(&conn, "run", 1600000000, 1, None, None).unwrap();
+        assert_eq!(
+            log_get_last_inserted(&conn, None).unwrap().unwrap().id,
+            backdated
+        );
+        assert_eq!(
+            log_get_last_inserted(&conn, Some("run"))
+                .unwrap()
+                .unwrap()
+                .id,
+            backdated
+        );
+
+        let read = log_insert_at(&conn, "read", 1500000000, 1, None, None).unwrap();
+        assert_eq!(
+            log_get_last_inserted(&conn, None).unwrap().unwrap().id,
+            read
+        );
+        assert_eq!(
+            log_get_last_inserted(&conn, Some("run"))
+                .unwrap()
+                .unwrap()
+                .id,
+            backdated
+        );
+    }
+
+    #[test]
+    fn migrate_newer_db_fails() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION + 1)
+            .unwrap();
+        assert!(migrate(&mut conn).is_err());
+        assert_eq!(user_version(&conn), SCHEMA_VERSION + 1);
+    }
+}
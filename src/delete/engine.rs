@@ -3,18 +3,23 @@ use crate::delete::cli::DeleteCli;
 use crate::engine::Engine;
 use crate::prompt;
 use anyhow::{anyhow, Context};
+use std::path::PathBuf;
 
-pub fn get_engine(cli: DeleteCli) -> Box<dyn Engine> {
-    Box::new(DeleteEngine { habit: cli.habit })
+pub fn get_engine(cli: DeleteCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(DeleteEngine {
+        db_path,
+        habit: cli.habit,
+    })
 }
 
 struct DeleteEngine {
+    db_path: PathBuf,
     habit: String,
 }
 
 impl Engine for DeleteEngine {
     fn run(&mut self) -> anyhow::Result<()> {
-        let conn = db::open_db()?;
+        let conn = db::open_db(&self.db_path)?;
 
         // check if habit exists in db, if not error
         if !db::habit_exists(&conn, &self.habit)? {
@@ -23,8 +28,8 @@ impl Engine for DeleteEngine {
 
         // ask for confirmation
         let confirmed = prompt::ask_for_confirmation(
-            &format!("Are you sure? All data for '{}' will be lost. Consider exporting it before with 'habit export {}'.",
-                self.habit, self.habit)
+            &format!("Are you sure? All data for '{}' will be lost. Consider archiving it instead with 'habit archive {}', or exporting it before with 'habit export {}'.",
+                self.habit, self.habit, self.habit)
         )?;
 
         // delete habit
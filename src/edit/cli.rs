@@ -19,6 +19,9 @@ pub struct EditCli {
 pub enum What {
     Name,
     Description,
-    Days,
+    #[value(alias = "days")]
+    Schedule,
     At,
+    Unit,
+    Target,
 }
@@ -4,22 +4,25 @@ use anyhow::anyhow;
 
 use crate::edit::cli::EditCli;
 use crate::engine::Engine;
+use std::path::PathBuf;
 
-pub fn get_engine(cli: EditCli) -> Box<dyn Engine> {
+pub fn get_engine(cli: EditCli, db_path: PathBuf) -> Box<dyn Engine> {
     Box::new(EditEngine {
+        db_path,
         habit: cli.habit,
         what: cli.what,
     })
 }
 
 struct EditEngine {
+    db_path: PathBuf,
     habit: String,
     what: What,
 }
 
 impl Engine for EditEngine {
     fn run(&mut self) -> anyhow::Result<()> {
-        let conn = db::open_db()?;
+        let conn = db::open_db(&self.db_path)?;
 
         // check if habit exists in db, if not error
         if !db::habit_exists(&conn, &self.habit)? {
@@ -29,7 +32,7 @@ impl Engine for EditEngine {
         // show input depending on what, then update db
         match self.what {
             What::Name => {
-                let new_name = prompt::prompt_habit_name()?;
+                let new_name = prompt::prompt_habit_name(&conn)?;
                 db::habit_update_name(&conn, &self.habit, &new_name)?;
                 println!("Name successfully updated!");
                 Ok(())
@@ -40,18 +43,31 @@ impl Engine for EditEngine {
                 println!("Description successfully updated!");
                 Ok(())
             }
-            What::Days => {
-                let new_days = prompt::prompt_habit_days()?;
-                db::habit_update_days(&conn, &self.habit, &new_days)?;
-                println!("Days successfully updated!");
+            What::Schedule => {
+                let new_schedule = prompt::prompt_habit_schedule()?;
+                db::habit_update_schedule(&conn, &self.habit, &new_schedule)?;
+                println!("Schedule successfully updated!");
                 Ok(())
             }
             What::At => {
-                let at = prompt::prompt_habit_at()?;
+                let habit = db::habit_get_by_name(&conn, &self.habit)?;
+                let at = prompt::prompt_habit_at(Some(&habit.at))?;
                 db::habit_update_at(&conn, &self.habit, &at)?;
                 println!("At successfully updated!");
                 Ok(())
             }
+            What::Unit => {
+                let unit = prompt::prompt_habit_unit()?;
+                db::habit_update_unit(&conn, &self.habit, unit.as_deref())?;
+                println!("Unit successfully updated!");
+                Ok(())
+            }
+            What::Target => {
+                let target = prompt::prompt_habit_target()?;
+                db::habit_update_target(&conn, &self.habit, target)?;
+                println!("Target successfully updated!");
+                Ok(())
+            }
         }
     }
 }
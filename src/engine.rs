@@ -1,22 +1,96 @@
-use crate::cli;
+use crate::archive;
+use crate::checkin;
+use crate::cli::{self, Command};
+use crate::config;
+use crate::config::Config;
+use crate::db;
 use crate::delete;
 use crate::edit;
+use crate::export;
+use crate::history;
+use crate::import;
 use crate::list;
 use crate::log;
 use crate::new;
+use crate::pause;
+use crate::profile;
+use crate::remind;
+use crate::resume;
 use crate::show;
+use crate::stats;
+use crate::tag;
+use crate::timers;
+use crate::today;
+use crate::unarchive;
+use crate::undo;
+use crate::unlog;
+use crate::DATA_DIR;
+use std::fs;
+use std::path::PathBuf;
 
 pub trait Engine {
     fn run(&mut self) -> anyhow::Result<()>;
+
+    /// The code to exit with once the engine ran successfully.
+    fn exit_code(&self) -> u8 {
+        0
+    }
 }
 
-pub fn get_engine(cli: cli::Cli) -> Box<dyn Engine> {
+/// Get the engine running the command of `cli`.
+///
+/// Settings and profiles are managed without loading the config nor opening any database,
+/// so that 'habit config' and 'habit profile' still work to fix those when they are broken.
+/// For the other commands, the database to use is made or updated first.
+pub fn get_engine(cli: cli::Cli) -> anyhow::Result<Box<dyn Engine>> {
     match cli.command {
-        crate::cli::Command::New(cli) => new::get_engine(cli),
-        crate::cli::Command::Edit(cli) => edit::get_engine(cli),
-        crate::cli::Command::Delete(cli) => delete::get_engine(cli),
-        crate::cli::Command::List(cli) => list::get_engine(cli),
-        crate::cli::Command::Log(cli) => log::get_engine(cli),
-        crate::cli::Command::Show(cli) => show::get_engine(cli),
+        Command::Config(cli) => return Ok(config::get_engine(cli)),
+        Command::Profile(cli) => return Ok(profile::get_engine(cli)),
+        _ => {}
+    }
+    let config = Config::load()?;
+    let db_path = prepare_db(&cli)?;
+
+    Ok(match cli.command {
+        Command::New(cli) => new::get_engine(cli, db_path),
+        Command::Edit(cli) => edit::get_engine(cli, db_path),
+        Command::Delete(cli) => delete::get_engine(cli, db_path),
+        Command::Archive(cli) => archive::get_engine(cli, db_path),
+        Command::Unarchive(cli) => unarchive::get_engine(cli, db_path),
+        Command::Pause(cli) => pause::get_engine(cli, db_path, config),
+        Command::Resume(cli) => resume::get_engine(cli, db_path),
+        Command::Tag(cli) => tag::get_engine(cli, db_path),
+        Command::List(cli) => list::get_engine(cli, db_path, config),
+        Command::Log(cli) => log::get_engine(cli, db_path, config),
+        Command::Checkin(_) => checkin::get_engine(db_path, config),
+        Command::Unlog(cli) => unlog::get_engine(cli, db_path),
+        Command::Undo(cli) => undo::get_engine(cli, db_path),
+        Command::History(cli) => history::get_engine(cli, db_path, config),
+        Command::Show(cli) => show::get_engine(cli, db_path, config),
+        Command::Stats(cli) => stats::get_engine(cli, db_path, config),
+        Command::Today(cli) => today::get_engine(cli, db_path, config),
+        Command::Remind(cli) => remind::get_engine(cli, db_path, config),
+        Command::Schedule(cli) => timers::get_engine(cli, db_path),
+        Command::Export(cli) => export::get_engine(cli, db_path),
+        Command::Import(cli) => import::get_engine(cli, db_path),
+        Command::Config(_) | Command::Profile(_) => {
+            unreachable!("Config and profile commands are handled above.")
+        }
+    })
+}
+
+/// Make or update the database to use given the options of `cli`, and return its path.
+fn prepare_db(cli: &cli::Cli) -> anyhow::Result<PathBuf> {
+    // Make directories
+    fs::create_dir_all(DATA_DIR.clone())?;
+    let db_path = cli.db_path()?;
+    if let Some(db_dir) = db_path.parent() {
+        fs::create_dir_all(db_dir)?;
     }
+
+    // Make or update the DB.
+    let mut conn = db::open_db(&db_path)?;
+    db::migrate(&mut conn)?;
+
+    Ok(db_path)
 }
@@ -0,0 +1,7 @@
+mod cli;
+mod engine;
+mod format;
+
+pub use cli::{ExportCli, Format};
+pub use engine::get_engine;
+pub use format::{CsvLog, Export, ExportedHabit, ExportedLog, FORMAT_VERSION};
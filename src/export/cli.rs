@@ -0,0 +1,41 @@
+use clap::Args;
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Export habits and their logged reps.
+pub struct ExportCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to export.
+    ///
+    /// Defaults to all habits.
+    pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, value_enum, default_value_t = Format::Json)]
+    /// The format to export to.
+    ///
+    /// JSON contains everything about the habits and can be imported back
+    /// with 'habit import'. CSV contains one row per logged rep.
+    pub format: Format,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The file to write the export to.
+    ///
+    /// Defaults to the standard output.
+    pub output: Option<PathBuf>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long = "tag", value_name = "TAG", conflicts_with = "habit")]
+    /// Only export habits with this tag.
+    /// Can be given several times, for habits with any of the tags.
+    pub tags: Vec<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
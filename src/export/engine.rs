@@ -0,0 +1,123 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::export::{
+    CsvLog, Export, ExportCli, ExportedHabit, ExportedLog, Format, FORMAT_VERSION,
+};
+use anyhow::{anyhow, Context};
+use chrono::{Local, TimeZone};
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+pub fn get_engine(cli: ExportCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(ExportEngine {
+        db_path,
+        habit: cli.habit,
+        format: cli.format,
+        output: cli.output,
+        tags: cli.tags,
+    })
+}
+
+struct ExportEngine {
+    db_path: PathBuf,
+    habit: Option<String>,
+    format: Format,
+    output: Option<PathBuf>,
+    tags: Vec<String>,
+}
+
+impl Engine for ExportEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // select the habits to export, along with their logs
+        let habits = match self.habit {
+            Some(ref habit_name) => vec![db::habit_get_by_name(&conn, habit_name)?],
+            None => db::habit_get_all(&conn)?
+                .into_iter()
+                .filter(|habit| habit.matches_tags(&self.tags))
+                .collect(),
+        };
+        let mut exported_habits = Vec::new();
+        for habit in habits {
+            let logs = db::get_logs_for_habit(&conn, &habit.name)?
+                .into_iter()
+                .map(|log| ExportedLog {
+                    timestamp: log.created,
+                    value: log.value,
+                    note: log.note,
+                    rating: log.rating,
+                })
+                .collect();
+            exported_habits.push(ExportedHabit { habit, logs });
+        }
+
+        // write them
+        let writer: Box<dyn Write> =
+            match self.output {
+                Some(ref path) => Box::new(File::create(path).with_context(|| {
+                    format!("Failed to create file {}.", path.to_string_lossy())
+                })?),
+                None => Box::new(io::stdout()),
+            };
+        match self.format {
+            Format::Json => write_json(writer, &exported_habits)?,
+            Format::Csv => write_csv(writer, &exported_habits)?,
+        }
+
+        if let Some(ref path) = self.output {
+            println!(
+                "{} {} successfully exported to {}.",
+                exported_habits.len(),
+                if exported_habits.len() <= 1 {
+                    "habit"
+                } else {
+                    "habits"
+                },
+                path.to_string_lossy()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn write_json(mut writer: impl Write, habits: &[ExportedHabit]) -> anyhow::Result<()> {
+    let export = Export {
+        version: FORMAT_VERSION,
+        habits: habits.to_vec(),
+    };
+    serde_json::to_writer_pretty(&mut writer, &export)
+        .with_context(|| "Failed to write JSON export.")?;
+    writeln!(writer)?;
+
+    Ok(())
+}
+
+fn write_csv(writer: impl Write, habits: &[ExportedHabit]) -> anyhow::Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for exported_habit in habits {
+        for log in exported_habit.logs.iter() {
+            let timestamp = Local
+                .timestamp_opt(log.timestamp, 0)
+                .single()
+                .ok_or_else(|| anyhow!("Invalid log timestamp {} in database.", log.timestamp))?
+                .to_rfc3339();
+            csv_writer
+                .serialize(CsvLog {
+                    habit: exported_habit.habit.name.clone(),
+                    timestamp,
+                    value: log.value,
+                    note: log.note.clone(),
+                    rating: log.rating,
+                })
+                .with_context(|| "Failed to write CSV export.")?;
+        }
+    }
+    csv_writer
+        .flush()
+        .with_context(|| "Failed to write CSV export.")?;
+
+    Ok(())
+}
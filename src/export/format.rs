@@ -0,0 +1,102 @@
+use crate::habit::Habit;
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON export format.
+/// Bump it whenever a change makes previous exports unreadable,
+/// or new exports unreadable by previous versions of habit.
+/// Version 2 added amounts to logs, version 3 schedules to habits,
+/// version 4 several times to habits.
+/// Archived habits, pauses, tags and notes and ratings of logs are optional,
+/// and ignored by previous versions.
+pub const FORMAT_VERSION: u32 = 4;
+
+/// A JSON export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Export {
+    pub version: u32,
+    pub habits: Vec<ExportedHabit>,
+}
+
+/// A habit along with its logged reps.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportedHabit {
+    #[serde(flatten)]
+    pub habit: Habit,
+    pub logs: Vec<ExportedLog>,
+}
+
+/// A logged rep.
+/// Version 1 exports only had the timestamps of logs, which are still accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "ExportedLogRepr")]
+pub struct ExportedLog {
+    /// Unix timestamp of the logged rep.
+    pub timestamp: i64,
+    /// The amount done, 1 for habits that are not measured.
+    pub value: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// From 1 to 5.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ExportedLogRepr {
+    Timestamp(i64),
+    Full {
+        timestamp: i64,
+        value: u32,
+        #[serde(default)]
+        note: Option<String>,
+        #[serde(default)]
+        rating: Option<u8>,
+    },
+}
+
+impl From<ExportedLogRepr> for ExportedLog {
+    fn from(repr: ExportedLogRepr) -> Self {
+        match repr {
+            ExportedLogRepr::Timestamp(timestamp) => ExportedLog {
+                timestamp,
+                value: 1,
+                note: None,
+                rating: None,
+            },
+            ExportedLogRepr::Full {
+                timestamp,
+                value,
+                note,
+                rating,
+            } => ExportedLog {
+                timestamp,
+                value,
+                note,
+                rating,
+            },
+        }
+    }
+}
+
+/// A row of a CSV export, i.e. a logged rep.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvLog {
+    pub habit: String,
+    /// RFC 3339 date and time of the logged rep.
+    pub timestamp: String,
+    /// The amount done, 1 for habits that are not measured.
+    /// Optional when importing.
+    #[serde(default = "default_value")]
+    pub value: u32,
+    /// Optional, empty if none.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// From 1 to 5, optional, empty if none.
+    #[serde(default)]
+    pub rating: Option<u8>,
+}
+
+fn default_value() -> u32 {
+    1
+}
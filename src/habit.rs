@@ -1,39 +1,394 @@
+use std::collections::{HashMap, HashSet};
 use std::{fmt, str::FromStr};
 
+use crate::schedule::{Period, Schedule};
+use crate::times::Times;
 use crate::utils;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, NaiveTime, Weekday};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 lazy_static! {
-    static ref AT_RE: Regex = Regex::new(r"(?<hour>\d\d):(?<minutes>\d\d)").unwrap();
+    static ref AT_RE: Regex = Regex::new(r"^(?<hour>\d\d):(?<minutes>\d\d)$").unwrap();
 }
 
 // Habit
 // -----
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Habit {
     pub name: String,
     pub description: String,
-    pub days: Vec<Day>,
-    pub at: At,
+    /// Habits used to only have days of the week.
+    #[serde(alias = "days")]
+    pub schedule: Schedule,
+    /// Habits used to have a single time.
+    pub at: Times,
+    /// What the amounts logged for the habit are counted in (e.g. pages),
+    /// if it is measured. Reps of unmeasured habits all have an amount of 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// The amount to reach at each of the times of a day for the habit to be done that day.
+    /// Defaults to 1, i.e. a single rep. Never 0.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_target"
+    )]
+    pub target: Option<u32>,
+    /// Archived habits are kept along with their logs, but are not tracked anymore.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+    /// The periods the habit was, is or will be paused for, in order.
+    /// The habit is not scheduled on the days of those.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pauses: Vec<Pause>,
+    /// The tags of the habit (e.g. health, work), in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Habit {
-    pub fn new(name: String, description: String, days: Vec<Day>, at: At) -> Self {
+    pub fn new(name: String, description: String, schedule: Schedule, at: Times) -> Self {
         Self {
             name,
             description,
-            days,
+            schedule,
             at,
+            unit: None,
+            target: None,
+            archived: false,
+            pauses: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Whether the habit has any of `tags`, always true if there are none,
+    /// so that no tags given means no filtering.
+    pub fn matches_tags(&self, tags: &[String]) -> bool {
+        tags.is_empty() || tags.iter().any(|tag| self.tags.contains(tag))
+    }
+
+    /// Whether the habit can be performed on `date` as scheduled (see Schedule::is_due_on),
+    /// and is not paused then.
+    pub fn is_due_on(&self, date: &NaiveDate) -> bool {
+        self.schedule.is_due_on(date) && !self.is_paused_on(date)
+    }
+
+    /// The pause the habit is in on `date`, if any.
+    pub fn pause_on(&self, date: &NaiveDate) -> Option<&Pause> {
+        self.pauses
+            .iter()
+            .find(|pause| pause.start <= *date && *date <= pause.until)
+    }
+
+    pub fn is_paused_on(&self, date: &NaiveDate) -> bool {
+        self.pause_on(date).is_some()
+    }
+
+    /// Whether the habit is tracked on `date`, i.e. it is neither archived nor paused.
+    pub fn is_active_on(&self, date: &NaiveDate) -> bool {
+        !self.archived && !self.is_paused_on(date)
+    }
+
+    /// Pause the habit from `start` to `until` (included), replacing the pauses
+    /// that overlap it: those started before `start` end the day before,
+    /// and the others are dropped. Pauses outside of it are kept.
+    pub fn pause(&mut self, start: NaiveDate, until: NaiveDate) {
+        self.pauses
+            .retain(|pause| pause.start < start || until < pause.start);
+        for pause in self.pauses.iter_mut() {
+            if pause.start < start {
+                pause.until = pause.until.min(start - Days::new(1));
+            }
+        }
+        self.pauses.push(Pause { start, until });
+        self.pauses.sort();
+    }
+
+    /// End the pauses that are not over by `date`, so that the habit is not paused
+    /// from `date` on. Returns whether there were any.
+    pub fn end_pauses(&mut self, date: NaiveDate) -> bool {
+        let pauses = self.pauses.clone();
+        self.pauses.retain(|pause| pause.start < date);
+        for pause in self.pauses.iter_mut() {
+            pause.until = pause.until.min(date - Days::new(1));
+        }
+
+        self.pauses != pauses
+    }
+
+    /// The days from `first` to `last` (included) on which the habit was, or is,
+    /// expected to be done, given the amount logged per day.
+    ///
+    /// For habits scheduled on fixed days, those are the days it is due on.
+    /// For habits to perform a number of times per period, those are the days
+    /// it was done on, and, for periods that are over (before `today`) in which
+    /// it was not done enough, as many of the last days it was not done on as missing.
+    pub fn expected_days(
+        &self,
+        amount_per_day: &HashMap<NaiveDate, usize>,
+        first: NaiveDate,
+        last: NaiveDate,
+        today: NaiveDate,
+        week_start: &Day,
+    ) -> HashSet<NaiveDate> {
+        let in_range = |day: &NaiveDate| first <= *day && *day <= last;
+        let Some(mut period) = self.schedule.period_of(&first, week_start) else {
+            return first
+                .iter_days()
+                .take_while(|day| *day <= last)
+                .filter(|day| self.is_due_on(day))
+                .collect();
+        };
+
+        let mut expected = HashSet::new();
+        while period.first <= last {
+            // days the habit is paused on cannot be missed
+            let (done, not_done): (Vec<NaiveDate>, Vec<NaiveDate>) = period
+                .days()
+                .filter(|day| self.is_due_on(day) || self.is_done_on(amount_per_day, day))
+                .partition(|day| self.is_done_on(amount_per_day, day));
+            let n_missing = (period.n as usize).saturating_sub(done.len());
+            expected.extend(done.into_iter().filter(in_range));
+            if period.last < today {
+                expected.extend(not_done.into_iter().rev().take(n_missing).filter(in_range));
+            }
+
+            let next_first = period.last + Days::new(1);
+            period = self
+                .schedule
+                .period_of(&next_first, week_start)
+                .expect("All periods of a schedule should have a period.");
+        }
+
+        expected
+    }
+
+    /// For habits to perform a number of times per period, the period containing `date`
+    /// along with the number of days the habit was done on in it, `None` for other habits.
+    pub fn period_progress(
+        &self,
+        amount_per_day: &HashMap<NaiveDate, usize>,
+        date: &NaiveDate,
+        week_start: &Day,
+    ) -> Option<(Period, usize)> {
+        let period = self.schedule.period_of(date, week_start)?;
+        let n_done = period
+            .days()
+            .filter(|day| self.is_done_on(amount_per_day, day))
+            .count();
+
+        Some((period, n_done))
+    }
+
+    /// Whether the habit was done on `date`, given the amount logged per day.
+    pub fn is_done_on(&self, amount_per_day: &HashMap<NaiveDate, usize>, date: &NaiveDate) -> bool {
+        amount_per_day
+            .get(date)
+            .is_some_and(|amount| self.is_done_with(*amount, date))
+    }
+
+    /// Whether the habit is measured, i.e. whether reps are logged with an amount.
+    pub fn is_measured(&self) -> bool {
+        self.unit.is_some()
+    }
+
+    /// The amount to reach at each of the times of a day.
+    pub fn target_per_time(&self) -> usize {
+        self.target.unwrap_or(1) as usize
+    }
+
+    /// The amount to reach on `date` for the habit to be done that day,
+    /// i.e. the target for each of the times of that day.
+    pub fn target_on(&self, date: &NaiveDate) -> usize {
+        self.target_per_time() * self.at.on(date).len()
+    }
+
+    /// The number of times of `date` for which `amount` logged that day is enough,
+    /// the times being done in order.
+    pub fn n_times_done_with(&self, amount: usize, date: &NaiveDate) -> usize {
+        (amount / self.target_per_time()).min(self.at.on(date).len())
+    }
+
+    /// Whether `amount` logged on `date` is enough for the habit to be done that day.
+    pub fn is_done_with(&self, amount: usize, date: &NaiveDate) -> bool {
+        amount >= self.target_on(date)
+    }
+
+    /// Describe `amount` in the unit of the habit, e.g. "25 pages" or "3 reps".
+    pub fn display_amount(&self, amount: usize) -> String {
+        match self.unit {
+            Some(ref unit) => format!("{} {}", amount, unit),
+            None => format!("{} {}", amount, if amount <= 1 { "rep" } else { "reps" }),
+        }
+    }
+}
+
+/// Deserialize the target of a habit, which must be positive
+/// since amounts are divided by it.
+fn deserialize_target<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u32>, D::Error> {
+    match Option::<u32>::deserialize(deserializer)? {
+        Some(0) => Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Unsigned(0),
+            &"a positive target",
+        )),
+        target => Ok(target),
+    }
+}
+
+// Pause
+// -----
+
+/// A period a habit is paused for, from `start` to `until` (included).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pause {
+    pub start: NaiveDate,
+    pub until: NaiveDate,
+}
+
+/// The form pauses are stored as, e.g. '2024-07-01..2024-07-14'.
+impl fmt::Display for Pause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}..{}",
+            self.start.format("%Y-%m-%d"),
+            self.until.format("%Y-%m-%d")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePauseError;
+
+impl fmt::Display for ParsePauseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Wrong format. Should be 'YYYY-MM-DD..YYYY-MM-DD', the start not being after the end."
+        )
+    }
+}
+
+impl std::error::Error for ParsePauseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        self.source()
+    }
+}
+
+impl FromStr for Pause {
+    type Err = ParsePauseError;
+
+    fn from_str(s: &str) -> Result<Self, ParsePauseError> {
+        let (start, until) = s.split_once("..").ok_or(ParsePauseError)?;
+        let start =
+            NaiveDate::parse_from_str(start.trim(), "%Y-%m-%d").map_err(|_| ParsePauseError)?;
+        let until =
+            NaiveDate::parse_from_str(until.trim(), "%Y-%m-%d").map_err(|_| ParsePauseError)?;
+        if start > until {
+            return Err(ParsePauseError);
         }
+
+        Ok(Pause { start, until })
+    }
+}
+
+// Serialized as a string, like it is stored.
+impl Serialize for Pause {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Pause {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Pause::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+// Log
+// ---
+
+/// A logged rep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Log {
+    pub id: i64,
+    /// The name of the habit the rep is for.
+    pub habit: String,
+    /// Unix timestamp of when the rep was done.
+    pub created: i64,
+    /// The amount done, always 1 for habits that are not measured.
+    pub value: u32,
+    /// A note about the rep, e.g. how it went.
+    pub note: Option<String>,
+    /// How the rep went, from 1 to 5.
+    pub rating: Option<u8>,
+}
+
+impl Log {
+    pub fn new(id: i64, habit: String, created: i64, value: u32) -> Self {
+        Self {
+            id,
+            habit,
+            created,
+            value,
+            note: None,
+            rating: None,
+        }
+    }
+
+    /// The rating and note of the rep, e.g. '(4/5) felt great',
+    /// `None` if it has neither.
+    pub fn annotation(&self) -> Option<String> {
+        match (self.rating, &self.note) {
+            (Some(rating), Some(note)) => Some(format!("({}/5) {}", rating, note)),
+            (Some(rating), None) => Some(format!("({}/5)", rating)),
+            (None, Some(note)) => Some(note.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// The local date and time of when the rep was done.
+    pub fn datetime(&self) -> DateTime<Local> {
+        DateTime::from_timestamp(self.created, 0)
+            .expect("Log timestamps should be valid.")
+            .with_timezone(&Local)
+    }
+}
+
+impl fmt::Display for Log {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let datetime = self.datetime();
+        write!(
+            f,
+            "log {} for '{}' on {} {}",
+            self.id,
+            self.habit,
+            Day::from(datetime.weekday()),
+            datetime.format("%Y-%m-%d %H:%M")
+        )?;
+        if self.value != 1 {
+            write!(f, " (amount of {})", self.value)?;
+        }
+        Ok(())
     }
 }
 
 // At
 // --
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct At {
     pub hour: u8,
     pub minutes: u8,
@@ -49,6 +404,11 @@ impl At {
             Ok(At { hour, minutes })
         }
     }
+
+    pub fn time(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.hour.into(), self.minutes.into(), 0)
+            .expect("Hour and minutes should be valid.")
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -111,6 +471,20 @@ impl fmt::Display for At {
     }
 }
 
+// Serialized as a 'hh:mm' string, like it is input.
+impl Serialize for At {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for At {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        At::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseAtError {
     WrongFormat,
@@ -145,7 +519,7 @@ impl std::error::Error for ParseAtError {
 // Day
 // ---
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub enum Day {
     Monday,
     Tuesday,
@@ -170,22 +544,275 @@ impl fmt::Display for Day {
     }
 }
 
+// Deserialized like it is input, e.g. 'Monday', 'monday' or 'mon'.
+impl<'de> Deserialize<'de> for Day {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Day::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl From<Weekday> for Day {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Mon => Day::Monday,
+            Weekday::Tue => Day::Tuesday,
+            Weekday::Wed => Day::Wednesday,
+            Weekday::Thu => Day::Thursday,
+            Weekday::Fri => Day::Friday,
+            Weekday::Sat => Day::Saturday,
+            Weekday::Sun => Day::Sunday,
+        }
+    }
+}
+
+impl From<Day> for Weekday {
+    fn from(day: Day) -> Self {
+        match day {
+            Day::Monday => Weekday::Mon,
+            Day::Tuesday => Weekday::Tue,
+            Day::Wednesday => Weekday::Wed,
+            Day::Thursday => Weekday::Thu,
+            Day::Friday => Weekday::Fri,
+            Day::Saturday => Weekday::Sat,
+            Day::Sunday => Weekday::Sun,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ParseDayError;
 
+impl fmt::Display for ParseDayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unknown day. Should be a day name like 'Monday' or 'mon', 'daily', 'weekdays' or 'weekends'."
+        )
+    }
+}
+
+impl std::error::Error for ParseDayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        self.source()
+    }
+}
+
+/// Parses full day names like they are displayed ("Monday"),
+/// as well as their first three letters ("mon"), ignoring case.
 impl FromStr for Day {
     type Err = ParseDayError;
 
     fn from_str(s: &str) -> Result<Self, ParseDayError> {
-        match s {
-            "Monday" => Ok(Self::Monday),
-            "Tuesday" => Ok(Self::Tuesday),
-            "Wednesday" => Ok(Self::Wednesday),
-            "Thursday" => Ok(Self::Thursday),
-            "Friday" => Ok(Self::Friday),
-            "Saturday" => Ok(Self::Saturday),
-            "Sunday" => Ok(Self::Sunday),
+        match s.to_lowercase().as_str() {
+            "monday" | "mon" => Ok(Self::Monday),
+            "tuesday" | "tue" => Ok(Self::Tuesday),
+            "wednesday" | "wed" => Ok(Self::Wednesday),
+            "thursday" | "thu" => Ok(Self::Thursday),
+            "friday" | "fri" => Ok(Self::Friday),
+            "saturday" | "sat" => Ok(Self::Saturday),
+            "sunday" | "sun" => Ok(Self::Sunday),
             _ => Err(ParseDayError),
         }
     }
 }
+
+/// Parse a comma-separated list of days (see Day::from_str),
+/// where "daily", "weekdays" and "weekends" can be used as shorthands.
+/// The days are returned in order, without duplicates.
+pub fn parse_days(s: &str) -> Result<Vec<Day>, ParseDayError> {
+    let mut days = Vec::new();
+    for day_str in s.split(',').map(|d| d.trim()) {
+        match day_str.to_lowercase().as_str() {
+            "daily" => days.extend([
+                Day::Monday,
+                Day::Tuesday,
+                Day::Wednesday,
+                Day::Thursday,
+                Day::Friday,
+                Day::Saturday,
+                Day::Sunday,
+            ]),
+            "weekdays" => days.extend([
+                Day::Monday,
+                Day::Tuesday,
+                Day::Wednesday,
+                Day::Thursday,
+                Day::Friday,
+            ]),
+            "weekends" => days.extend([Day::Saturday, Day::Sunday]),
+            _ => days.push(Day::from_str(day_str)?),
+        }
+    }
+    days.sort();
+    days.dedup();
+
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_habit(schedule: &str) -> Habit {
+        Habit::new(
+            String::from("run"),
+            String::from("Go for a run"),
+            Schedule::from_str(schedule).unwrap(),
+            Times::from_str("08:00").unwrap(),
+        )
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::from_str(s).unwrap()
+    }
+
+    fn dates(days: &[&str]) -> HashSet<NaiveDate> {
+        days.iter().map(|day| date(day)).collect()
+    }
+
+    fn amount_per_day(days: &[&str]) -> HashMap<NaiveDate, usize> {
+        days.iter().map(|day| (date(day), 1)).collect()
+    }
+
+    #[test]
+    fn pause_keeps_pauses_it_does_not_overlap() {
+        let pause = |start: &str, until: &str| Pause {
+            start: date(start),
+            until: date(until),
+        };
+        let mut habit = new_habit("daily");
+        habit.pause(date("2024-01-10"), date("2024-01-20"));
+        habit.pause(date("2024-03-01"), date("2024-03-31"));
+        habit.pause(date("2024-06-01"), date("2024-06-30"));
+
+        // pausing in the past, between the other pauses
+        habit.pause(date("2024-02-01"), date("2024-02-10"));
+        assert_eq!(
+            habit.pauses,
+            vec![
+                pause("2024-01-10", "2024-01-20"),
+                pause("2024-02-01", "2024-02-10"),
+                pause("2024-03-01", "2024-03-31"),
+                pause("2024-06-01", "2024-06-30"),
+            ]
+        );
+
+        // the pause started before is cut short, the one started during is replaced
+        habit.pause(date("2024-03-15"), date("2024-06-10"));
+        assert_eq!(
+            habit.pauses,
+            vec![
+                pause("2024-01-10", "2024-01-20"),
+                pause("2024-02-01", "2024-02-10"),
+                pause("2024-03-01", "2024-03-14"),
+                pause("2024-03-15", "2024-06-10"),
+            ]
+        );
+
+        // pausing again from the same day shortens the pause
+        habit.pause(date("2024-03-15"), date("2024-03-20"));
+        assert_eq!(habit.pauses[3], pause("2024-03-15", "2024-03-20"));
+        assert_eq!(habit.pauses.len(), 4);
+    }
+
+    #[test]
+    fn expected_days_of_fixed_days() {
+        let habit = new_habit("every 3 days from 2024-05-01");
+        assert_eq!(
+            habit.expected_days(
+                &HashMap::new(),
+                date("2024-04-28"),
+                date("2024-05-07"),
+                date("2024-05-20"),
+                &Day::Monday
+            ),
+            dates(&["2024-05-01", "2024-05-04", "2024-05-07"])
+        );
+
+        let habit = new_habit("monthly on 31");
+        assert_eq!(
+            habit.expected_days(
+                &HashMap::new(),
+                date("2024-02-01"),
+                date("2024-04-30"),
+                date("2024-05-20"),
+                &Day::Monday
+            ),
+            dates(&["2024-02-29", "2024-03-31", "2024-04-30"])
+        );
+    }
+
+    #[test]
+    fn expected_days_per_week() {
+        // weeks from 2024-05-06 and 2024-05-13, the second being the current one
+        let habit = new_habit("3 per week");
+        let amount_per_day = amount_per_day(&["2024-05-07", "2024-05-13"]);
+        assert_eq!(
+            habit.expected_days(
+                &amount_per_day,
+                date("2024-05-06"),
+                date("2024-05-19"),
+                date("2024-05-15"),
+                &Day::Monday
+            ),
+            // the last days of the week that is over count as missed,
+            // while there is still time this week
+            dates(&["2024-05-07", "2024-05-11", "2024-05-12", "2024-05-13"])
+        );
+    }
+
+    #[test]
+    fn expected_days_per_month() {
+        let habit = new_habit("2 per month");
+        let amount_per_day = amount_per_day(&["2024-02-01", "2024-02-10", "2024-02-11"]);
+        assert_eq!(
+            habit.expected_days(
+                &amount_per_day,
+                date("2024-02-01"),
+                date("2024-03-31"),
+                date("2024-05-20"),
+                &Day::Monday
+            ),
+            // done days count even beyond the number of times
+            dates(&[
+                "2024-02-01",
+                "2024-02-10",
+                "2024-02-11",
+                "2024-03-30",
+                "2024-03-31"
+            ])
+        );
+        let (period, n_done) = habit
+            .period_progress(&amount_per_day, &date("2024-02-20"), &Day::Monday)
+            .unwrap();
+        assert_eq!(
+            (period.first, period.last, n_done),
+            (date("2024-02-01"), date("2024-02-29"), 3)
+        );
+    }
+
+    #[test]
+    fn paused_days_are_not_expected() {
+        let mut habit = new_habit("3 per week");
+        habit.pause(date("2024-05-10"), date("2024-05-12"));
+        assert_eq!(
+            habit.expected_days(
+                &HashMap::new(),
+                date("2024-05-06"),
+                date("2024-05-12"),
+                date("2024-05-20"),
+                &Day::Monday
+            ),
+            dates(&["2024-05-07", "2024-05-08", "2024-05-09"])
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod cli;
+mod engine;
+
+pub use cli::HistoryCli;
+pub use engine::get_engine;
@@ -0,0 +1,36 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Show the logged reps, most recent first, with their notes and ratings.
+///
+/// Notes and ratings are given when logging a rep,
+/// e.g. 'habit log run --note "felt great, 5k PR" --rating 5'.
+pub struct HistoryCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit for which to show logged reps.
+    ///
+    /// Defaults to all habits.
+    pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, default_value_t = 20, conflicts_with = "all")]
+    /// The number of reps to show.
+    pub number: usize,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Show all logged reps.
+    pub all: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, action)]
+    /// Only show reps with a note or a rating.
+    pub notes: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long = "tag", value_name = "TAG", conflicts_with = "habit")]
+    /// Only show reps of habits with this tag.
+    /// Can be given several times, for habits with any of the tags.
+    pub tags: Vec<String>,
+}
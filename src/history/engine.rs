@@ -0,0 +1,122 @@
+use crate::config::Config;
+use crate::db;
+use crate::engine::Engine;
+use crate::habit::{Day, Habit, Log};
+use crate::history::cli::HistoryCli;
+use chrono::Datelike;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: HistoryCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(HistoryEngine {
+        db_path,
+        config,
+        habit: cli.habit,
+        number: if cli.all { None } else { Some(cli.number) },
+        notes: cli.notes,
+        tags: cli.tags,
+    })
+}
+
+struct HistoryEngine {
+    db_path: PathBuf,
+    config: Config,
+    habit: Option<String>,
+    /// The number of reps to show, all of them if `None`.
+    number: Option<usize>,
+    notes: bool,
+    tags: Vec<String>,
+}
+
+impl Engine for HistoryEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // errors if the habit does not exist
+        let (habits, logs) = match self.habit {
+            Some(ref habit_name) => (
+                vec![db::habit_get_by_name(&conn, habit_name)?],
+                db::get_logs_for_habit(&conn, habit_name)?,
+            ),
+            None => (db::habit_get_all(&conn)?, db::get_all_logs(&conn)?),
+        };
+        let habits: HashMap<String, Habit> = habits
+            .into_iter()
+            .filter(|habit| habit.matches_tags(&self.tags))
+            .map(|habit| (habit.name.clone(), habit))
+            .collect();
+        let logs: Vec<Log> = logs
+            .into_iter()
+            .rev()
+            .filter(|log| habits.contains_key(&log.habit))
+            .filter(|log| !self.notes || log.annotation().is_some())
+            .take(self.number.unwrap_or(usize::MAX))
+            .collect();
+
+        if logs.is_empty() {
+            println!(
+                "{}",
+                if self.notes {
+                    "No rep with a note or a rating."
+                } else {
+                    "No rep logged."
+                }
+                .bright_black()
+            );
+            return Ok(());
+        }
+
+        // amounts are only shown for measured habits, the others always having 1
+        let amounts: Vec<String> = logs
+            .iter()
+            .map(|log| {
+                let habit = &habits[&log.habit];
+                if habit.is_measured() {
+                    habit.display_amount(log.value as usize)
+                } else {
+                    String::new()
+                }
+            })
+            .collect();
+        let name_width = logs.iter().map(|log| log.habit.len()).max().unwrap_or(0);
+        let amount_width = amounts.iter().map(|amount| amount.len()).max().unwrap_or(0);
+
+        // reps grouped by day, most recent first
+        let mut last_date = None;
+        for (log, amount) in logs.iter().zip(amounts.iter()) {
+            let datetime = log.datetime();
+            let date = datetime.date_naive();
+            if last_date != Some(date) {
+                if last_date.is_some() {
+                    println!();
+                }
+                println!(
+                    "{}",
+                    format!(
+                        "{} {}",
+                        Day::from(date.weekday()),
+                        date.format(&self.config.date_format)
+                    )
+                    .bold()
+                );
+                last_date = Some(date);
+            }
+
+            let mut row = format!(
+                "    {}  {:<name_width$}",
+                datetime.format("%H:%M"),
+                log.habit
+            );
+            if amount_width > 0 {
+                row.push_str(&format!("  {:<amount_width$}", amount));
+            }
+            if let Some(annotation) = log.annotation() {
+                row.push_str(&format!("  {}", annotation));
+            }
+            println!("{}  {}", row, format!("(log {})", log.id).bright_black());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::export::Format;
+use clap::Args;
+use std::path::PathBuf;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Import habits and their logged reps.
+///
+/// Habits that do not exist yet are created, and logged reps
+/// are merged with the existing ones (already logged reps are skipped).
+pub struct ImportCli {
+    #[clap(verbatim_doc_comment)]
+    /// The file to import.
+    ///
+    /// Either a JSON file made with 'habit export',
+    /// or a CSV file with 'habit' and 'timestamp' columns.
+    /// Timestamps can be RFC 3339 dates and times, 'YYYY-MM-DD hh:mm[:ss]',
+    /// 'YYYY-MM-DD' or unix timestamps.
+    /// Habits of a CSV file must exist beforehand.
+    pub file: PathBuf,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, value_enum)]
+    /// The format of the file.
+    ///
+    /// Defaults to the one matching the file extension, or JSON.
+    pub format: Option<Format>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, action)]
+    /// Only report what would be imported, without changing anything.
+    pub dry_run: bool,
+}
@@ -0,0 +1,454 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::export::{CsvLog, Export, ExportedLog, Format, FORMAT_VERSION};
+use crate::habit::Habit;
+use crate::import::cli::ImportCli;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use colored::Colorize;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: ImportCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(ImportEngine {
+        db_path,
+        file: cli.file,
+        format: cli.format,
+        dry_run: cli.dry_run,
+    })
+}
+
+struct ImportEngine {
+    db_path: PathBuf,
+    file: PathBuf,
+    format: Option<Format>,
+    dry_run: bool,
+}
+
+/// A habit to import, along with its logs.
+struct ImportedHabit {
+    name: String,
+    /// The full habit if known, so that it can be created if missing.
+    habit: Option<Habit>,
+    logs: Vec<ExportedLog>,
+}
+
+impl Engine for ImportEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let format = self.format.unwrap_or_else(|| {
+            match self.file.extension().and_then(|ext| ext.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+                _ => Format::Json,
+            }
+        });
+        let file = File::open(&self.file)
+            .with_context(|| format!("Failed to open file {}.", self.file.to_string_lossy()))?;
+        let imported_habits = match format {
+            Format::Json => read_json(file)?,
+            Format::Csv => read_csv(file)?,
+        };
+
+        let mut conn = db::open_db(&self.db_path)?;
+        // Import everything or nothing.
+        let tx = conn.transaction()?;
+
+        let (n_created, n_imported_logs) = import(&tx, imported_habits, self.dry_run)?;
+
+        if self.dry_run {
+            println!("Dry run, nothing done.");
+        } else {
+            tx.commit()
+                .with_context(|| "Failed to commit imported data to database.")?;
+            println!(
+                "Import successful! {} {} created and {} {} imported.",
+                n_created,
+                if n_created <= 1 { "habit" } else { "habits" },
+                n_imported_logs,
+                if n_imported_logs <= 1 { "rep" } else { "reps" }
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Import `imported_habits` into the database of `conn`, creating the habits missing
+/// and logging the reps not logged yet, unless `dry_run`.
+/// Returns the number of habits created and of reps logged, or that would be.
+fn import(
+    conn: &Connection,
+    imported_habits: Vec<ImportedHabit>,
+    dry_run: bool,
+) -> anyhow::Result<(usize, usize)> {
+    // check that habits we cannot create already exist
+    let mut missing = Vec::new();
+    for imported_habit in imported_habits.iter() {
+        if imported_habit.habit.is_none() && !db::habit_exists(conn, &imported_habit.name)? {
+            missing.push(format!("'{}'", imported_habit.name));
+        }
+    }
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "Habits {} do not exist! Create them with 'habit new' before importing.",
+            missing.join(", ")
+        ));
+    }
+
+    let mut n_created = 0;
+    let mut n_imported_logs = 0;
+    for imported_habit in imported_habits {
+        let name = format!("'{}'", imported_habit.name).bold();
+
+        // Logs are identified by their timestamp and amount. Several reps can share both,
+        // so each one already in the database only matches one imported log.
+        let mut existing_logs: HashMap<(i64, u32), usize> = HashMap::new();
+        if db::habit_exists(conn, &imported_habit.name)? {
+            for log in db::get_logs_for_habit(conn, &imported_habit.name)? {
+                *existing_logs.entry((log.created, log.value)).or_default() += 1;
+            }
+        } else {
+            // Cannot be None, otherwise we would have returned above.
+            let habit = imported_habit.habit.as_ref().unwrap();
+            if !dry_run {
+                db::habit_insert(conn, habit)?;
+            }
+            println!(
+                "{} {}",
+                if dry_run { "Would create" } else { "Created" },
+                name
+            );
+            n_created += 1;
+        }
+
+        let n_logs = imported_habit.logs.len();
+        let mut new_logs: Vec<ExportedLog> = imported_habit
+            .logs
+            .into_iter()
+            .filter(
+                |log| match existing_logs.get_mut(&(log.timestamp, log.value)) {
+                    Some(n) if *n > 0 => {
+                        *n -= 1;
+                        false
+                    }
+                    _ => true,
+                },
+            )
+            .collect();
+        new_logs.sort_by_key(|log| (log.timestamp, log.value));
+        if !dry_run {
+            for log in new_logs.iter() {
+                db::log_insert_at(
+                    conn,
+                    &imported_habit.name,
+                    log.timestamp,
+                    log.value,
+                    log.note.as_deref(),
+                    log.rating,
+                )?;
+            }
+        }
+        println!(
+            "{} {} {} for habit {} ({} already logged).",
+            if dry_run { "Would import" } else { "Imported" },
+            new_logs.len(),
+            if new_logs.len() <= 1 { "rep" } else { "reps" },
+            name,
+            n_logs - new_logs.len()
+        );
+        n_imported_logs += new_logs.len();
+    }
+
+    Ok((n_created, n_imported_logs))
+}
+
+fn read_json(file: File) -> anyhow::Result<Vec<ImportedHabit>> {
+    let export: Export =
+        serde_json::from_reader(file).with_context(|| "Failed to read JSON export.")?;
+    if export.version > FORMAT_VERSION {
+        return Err(anyhow!(
+            "Export format version {} is not supported (latest supported is {}). Consider upgrading habit.",
+            export.version,
+            FORMAT_VERSION
+        ));
+    }
+
+    // targets of habits are checked when deserializing them
+    for exported_habit in export.habits.iter() {
+        if exported_habit.logs.iter().any(|log| log.value == 0) {
+            return Err(anyhow!(
+                "Invalid value 0 for a rep of habit '{}', it must be positive.",
+                exported_habit.habit.name
+            ));
+        }
+        if let Some(rating) = exported_habit
+            .logs
+            .iter()
+            .filter_map(|log| log.rating)
+            .find(|rating| !is_valid_rating(*rating))
+        {
+            return Err(anyhow!(
+                "Invalid rating {} for a rep of habit '{}', it must be from 1 to 5.",
+                rating,
+                exported_habit.habit.name
+            ));
+        }
+    }
+
+    Ok(export
+        .habits
+        .into_iter()
+        .map(|exported_habit| ImportedHabit {
+            name: exported_habit.habit.name.clone(),
+            habit: Some(exported_habit.habit),
+            logs: exported_habit.logs,
+        })
+        .collect())
+}
+
+fn read_csv(file: File) -> anyhow::Result<Vec<ImportedHabit>> {
+    let mut imported_habits: Vec<ImportedHabit> = Vec::new();
+    for (i, row) in csv::Reader::from_reader(file).deserialize().enumerate() {
+        // +2 for the header and because lines are numbered from 1
+        let line = i + 2;
+        let row: CsvLog = row.with_context(|| format!("Failed to read CSV line {}.", line))?;
+        let timestamp = parse_timestamp(&row.timestamp)
+            .with_context(|| format!("Invalid timestamp on CSV line {}.", line))?;
+        if row.value == 0 {
+            return Err(anyhow!(
+                "Invalid value on CSV line {}, it must be positive.",
+                line
+            ));
+        }
+        if row.rating.is_some_and(|rating| !is_valid_rating(rating)) {
+            return Err(anyhow!(
+                "Invalid rating on CSV line {}, it must be from 1 to 5.",
+                line
+            ));
+        }
+        let log = ExportedLog {
+            timestamp,
+            value: row.value,
+            note: row.note.filter(|note| !note.is_empty()),
+            rating: row.rating,
+        };
+
+        match imported_habits.iter_mut().find(|h| h.name == row.habit) {
+            Some(imported_habit) => imported_habit.logs.push(log),
+            None => imported_habits.push(ImportedHabit {
+                name: row.habit,
+                habit: None,
+                logs: vec![log],
+            }),
+        }
+    }
+
+    Ok(imported_habits)
+}
+
+fn is_valid_rating(rating: u8) -> bool {
+    (1..=5).contains(&rating)
+}
+
+/// Parse a timestamp of a CSV file into a unix timestamp.
+/// Dates and times without time zone are considered local.
+fn parse_timestamp(s: &str) -> anyhow::Result<i64> {
+    let s = s.trim();
+
+    if let Ok(timestamp) = s.parse::<i64>() {
+        return Ok(timestamp);
+    }
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+        return Ok(datetime.timestamp());
+    }
+
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| anyhow!("Unrecognized date and time '{}'.", s))?;
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|datetime| datetime.timestamp())
+        .ok_or_else(|| anyhow!("'{}' does not exist in the local time zone.", s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{self, ExportCli};
+    use crate::habit::Log;
+    use crate::schedule::Schedule;
+    use crate::times::Times;
+    use std::fs;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    /// A new empty temporary directory for test `name`.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("habit-test-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn new_db(path: &Path) -> Connection {
+        let mut conn = db::open_db(path).unwrap();
+        db::migrate(&mut conn).unwrap();
+        conn
+    }
+
+    /// A database with a measured habit and another one, both with logged reps,
+    /// two of which are identical, as when logging a habit twice at once.
+    fn source_db(path: &Path) -> Connection {
+        let conn = new_db(path);
+        let mut reading = Habit::new(
+            String::from("reading"),
+            String::from("Read a book"),
+            Schedule::from_str("daily").unwrap(),
+            Times::from_str("21:00").unwrap(),
+        );
+        reading.unit = Some(String::from("pages"));
+        reading.target = Some(20);
+        reading.tags = vec![String::from("learning")];
+        db::habit_insert(&conn, &reading).unwrap();
+        db::log_insert_at(&conn, "reading", 1715000000, 25, Some("dune"), Some(4)).unwrap();
+        db::log_insert_at(&conn, "reading", 1715100000, 10, None, None).unwrap();
+
+        let run = Habit::new(
+            String::from("run"),
+            String::from("Go for a run"),
+            Schedule::from_str("3 per week").unwrap(),
+            Times::from_str("08:00,sat 10:00").unwrap(),
+        );
+        db::habit_insert(&conn, &run).unwrap();
+        db::log_insert_at(&conn, "run", 1715000000, 1, None, Some(5)).unwrap();
+        db::log_insert_at(&conn, "run", 1715200000, 1, None, None).unwrap();
+        db::log_insert_at(&conn, "run", 1715200000, 1, None, None).unwrap();
+
+        conn
+    }
+
+    fn export(db_path: &Path, format: Format, output: &Path) {
+        let cli = ExportCli {
+            habit: None,
+            format,
+            output: Some(output.to_path_buf()),
+            tags: Vec::new(),
+        };
+        export::get_engine(cli, db_path.to_path_buf())
+            .run()
+            .unwrap();
+    }
+
+    /// The logs of all habits, with ids left out since they differ between databases.
+    fn logs(conn: &Connection) -> Vec<Log> {
+        db::get_all_logs(conn)
+            .unwrap()
+            .into_iter()
+            .map(|log| Log { id: 0, ..log })
+            .collect()
+    }
+
+    #[test]
+    fn json_export_round_trip() {
+        let dir = temp_dir("json");
+        let source = source_db(&dir.join("source.db"));
+        let export_path = dir.join("export.json");
+        export(&dir.join("source.db"), Format::Json, &export_path);
+        let read = || read_json(File::open(&export_path).unwrap()).unwrap();
+
+        // into an empty database
+        let target = new_db(&dir.join("target.db"));
+        assert_eq!(import(&target, read(), true).unwrap(), (2, 5));
+        assert!(db::habit_get_all(&target).unwrap().is_empty());
+        assert_eq!(import(&target, read(), false).unwrap(), (2, 5));
+        assert_eq!(
+            db::habit_get_all(&target).unwrap(),
+            db::habit_get_all(&source).unwrap()
+        );
+        assert_eq!(logs(&target), logs(&source));
+
+        // into the same database again, or the one exported
+        assert_eq!(import(&target, read(), true).unwrap(), (0, 0));
+        assert_eq!(import(&target, read(), false).unwrap(), (0, 0));
+        assert_eq!(logs(&target), logs(&source));
+        assert_eq!(import(&source, read(), false).unwrap(), (0, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn csv_export_round_trip() {
+        let dir = temp_dir("csv");
+        let source = source_db(&dir.join("source.db"));
+        let export_path = dir.join("export.csv");
+        export(&dir.join("source.db"), Format::Csv, &export_path);
+        let read = || read_csv(File::open(&export_path).unwrap()).unwrap();
+
+        // CSV exports only have logs, so habits must exist
+        let target = new_db(&dir.join("target.db"));
+        assert!(import(&target, read(), false).is_err());
+        for habit in db::habit_get_all(&source).unwrap() {
+            db::habit_insert(&target, &habit).unwrap();
+        }
+        assert_eq!(import(&target, read(), true).unwrap(), (0, 5));
+        assert!(logs(&target).is_empty());
+        assert_eq!(import(&target, read(), false).unwrap(), (0, 5));
+        assert_eq!(logs(&target), logs(&source));
+
+        assert_eq!(import(&target, read(), false).unwrap(), (0, 0));
+        assert_eq!(import(&source, read(), false).unwrap(), (0, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn identical_reps_are_imported_as_many_times_as_missing() {
+        let dir = temp_dir("identical");
+        let source = source_db(&dir.join("source.db"));
+        let export_path = dir.join("export.json");
+        export(&dir.join("source.db"), Format::Json, &export_path);
+        let read = || read_json(File::open(&export_path).unwrap()).unwrap();
+
+        // only one of the identical reps is already logged
+        let target = new_db(&dir.join("target.db"));
+        for habit in db::habit_get_all(&source).unwrap() {
+            db::habit_insert(&target, &habit).unwrap();
+        }
+        db::log_insert_at(&target, "run", 1715200000, 1, None, None).unwrap();
+        assert_eq!(import(&target, read(), false).unwrap(), (0, 4));
+        assert_eq!(logs(&target).len(), 5);
+        assert_eq!(
+            logs(&target)
+                .iter()
+                .filter(|log| log.created == 1715200000)
+                .count(),
+            2
+        );
+        assert_eq!(import(&target, read(), false).unwrap(), (0, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_timestamps() {
+        assert_eq!(parse_timestamp("1715000000").unwrap(), 1715000000);
+        assert_eq!(
+            parse_timestamp("2024-05-06T12:53:20+00:00").unwrap(),
+            1715000000
+        );
+        assert_eq!(
+            parse_timestamp("2024-05-06T14:53:20+02:00").unwrap(),
+            1715000000
+        );
+        assert!(parse_timestamp("yesterday").is_err());
+    }
+}
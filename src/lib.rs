@@ -1,15 +1,35 @@
+pub mod archive;
+pub mod checkin;
 pub mod cli;
+pub mod config;
 pub mod db;
 pub mod delete;
 pub mod edit;
 pub mod engine;
+pub mod export;
 pub mod habit;
+pub mod history;
+pub mod import;
 pub mod list;
 pub mod log;
 pub mod new;
+pub mod pause;
+pub mod profile;
 pub mod prompt;
+pub mod remind;
+pub mod resume;
+pub mod schedule;
 pub mod show;
+pub mod stats;
+pub mod streak;
+pub mod tag;
+pub mod timers;
+pub mod times;
+pub mod today;
 pub mod tui;
+pub mod unarchive;
+pub mod undo;
+pub mod unlog;
 pub mod utils;
 
 use clap::crate_name;
@@ -18,6 +38,10 @@ use lazy_static::lazy_static;
 use std::path::PathBuf;
 
 lazy_static! {
+    pub static ref CONFIG_DIR: PathBuf = ProjectDirs::from("", crate_name!(), crate_name!())
+        .unwrap()
+        .config_dir()
+        .to_path_buf();
     pub static ref DATA_DIR: PathBuf = ProjectDirs::from("", crate_name!(), crate_name!())
         .unwrap()
         .data_local_dir()
@@ -8,4 +8,21 @@ pub struct ListCli {
     #[clap(long, short, action)]
     // Verbose output, i.e. show all habit info, not just its name.
     pub verbose: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action, conflicts_with = "verbose")]
+    // Brief output, i.e. only show habit names,
+    // even if verbose output is the default (see 'habit config').
+    pub brief: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Also list archived habits, and habits paused today.
+    pub all: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long = "tag", value_name = "TAG")]
+    /// Only list habits with this tag.
+    /// Can be given several times, for habits with any of the tags.
+    pub tags: Vec<String>,
 }
@@ -1,48 +1,45 @@
+use crate::config::Config;
 use crate::db;
-use crate::habit::At;
+use crate::habit::Habit;
+use crate::streak::Streaks;
 use crate::utils;
-use anyhow::Context;
+use chrono::Local;
 use colored::Colorize;
-use std::str::FromStr;
 
 use crate::engine::Engine;
-use crate::habit::{Day, Habit};
 use crate::list::cli::ListCli;
+use std::path::PathBuf;
 
-pub fn get_engine(cli: ListCli) -> Box<dyn Engine> {
+pub fn get_engine(cli: ListCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
     Box::new(ListEngine {
-        verbose: cli.verbose,
+        db_path,
+        verbose: cli.verbose || (config.list_verbose && !cli.brief),
+        all: cli.all,
+        tags: cli.tags,
+        config,
     })
 }
 
 struct ListEngine {
+    db_path: PathBuf,
     verbose: bool,
+    all: bool,
+    tags: Vec<String>,
+    config: Config,
 }
 
 impl Engine for ListEngine {
     fn run(&mut self) -> anyhow::Result<()> {
-        let conn = db::open_db()?;
+        let conn = db::open_db(&self.db_path)?;
 
-        if self.verbose {
-            let mut stmt = conn
-                .prepare("SELECT name, description, days, hour, minutes FROM habit")
-                .with_context(|| "Failed to select habits from database.")?;
-
-            let habits = stmt.query_map([], |row| {
-                Ok(Habit::new(
-                    row.get::<usize, String>(0)?,
-                    row.get::<usize, String>(1)?,
-                    row.get::<usize, String>(2)?
-                        .split(' ')
-                        .map(|d_str| {
-                            Day::from_str(d_str).expect("There is a wrong day in database.")
-                        })
-                        .collect(),
-                    At::build(row.get::<usize, u8>(3)?, row.get::<usize, u8>(4)?)
-                        .expect("There is a wrong hour or wrong minutes in database."),
-                ))
-            })?;
+        let today = Local::now().date_naive();
+        let habits: Vec<Habit> = db::habit_get_all(&conn)?
+            .into_iter()
+            .filter(|habit| self.all || habit.is_active_on(&today))
+            .filter(|habit| habit.matches_tags(&self.tags))
+            .collect();
 
+        if self.verbose {
             let max_width = termsize::get()
                 .map(|size| size.cols)
                 .unwrap_or(u16::MAX)
@@ -50,7 +47,9 @@ impl Engine for ListEngine {
                 .unwrap_or(u16::MAX) as usize;
 
             for habit in habits {
-                let habit = habit?;
+                let amount_per_day = db::get_all_amount_per_day_for_habit(&conn, &habit.name)?;
+                let streaks =
+                    Streaks::compute(&habit, &amount_per_day, today, &self.config.week_start);
 
                 println!("{}", habit.name.bold());
                 for line in textwrap::wrap(&habit.description, max_width) {
@@ -58,10 +57,59 @@ impl Engine for ListEngine {
                 }
                 for line in textwrap::wrap(
                     &format!(
-                        "{} Each {} at {}.",
+                        "{} {} {}.",
                         ">".bright_black(),
-                        utils::display_days(&habit.days),
-                        habit.at
+                        habit.schedule.describe(),
+                        habit.at.describe()
+                    ),
+                    max_width,
+                ) {
+                    println!("    {}", line);
+                }
+                if habit.is_measured() {
+                    for line in textwrap::wrap(
+                        &format!(
+                            "{} Target of {} {}.",
+                            ">".bright_black(),
+                            habit.display_amount(habit.target_per_time()),
+                            if habit.at.slots().len() > 1 {
+                                "each time"
+                            } else {
+                                "a day"
+                            }
+                        ),
+                        max_width,
+                    ) {
+                        println!("    {}", line);
+                    }
+                }
+                if !habit.tags.is_empty() {
+                    for line in textwrap::wrap(
+                        &format!(
+                            "{} Tagged {}.",
+                            ">".bright_black(),
+                            utils::display_list(&habit.tags)
+                        ),
+                        max_width,
+                    ) {
+                        println!("    {}", line);
+                    }
+                }
+                if habit.archived {
+                    println!("    {} Archived.", ">".bright_black());
+                } else if let Some(pause) = habit.pause_on(&today) {
+                    println!(
+                        "    {} Paused until {}.",
+                        ">".bright_black(),
+                        pause.until.format(&self.config.date_format)
+                    );
+                }
+                for line in textwrap::wrap(
+                    &format!(
+                        "{} Current streak of {}, longest of {}.",
+                        ">".bright_black(),
+                        streaks.current.map_or(0, |streak| streak.length),
+                        streaks.longest.map_or(0, |streak| streak.length)
                     ),
                     max_width,
                 ) {
@@ -69,15 +117,22 @@ impl Engine for ListEngine {
                 }
             }
         } else {
-            let mut stmt = conn
-                .prepare("SELECT name FROM habit")
-                .with_context(|| "Failed to select habit names from database.")?;
-
-            let names = stmt.query_map([], |row| row.get::<usize, String>(0))?;
-
-            for name in names {
-                let name = name?;
-                println!("{}", name);
+            for habit in habits {
+                if habit.archived {
+                    println!("{} {}", habit.name, "(archived)".bright_black());
+                } else if let Some(pause) = habit.pause_on(&today) {
+                    println!(
+                        "{} {}",
+                        habit.name,
+                        format!(
+                            "(paused until {})",
+                            pause.until.format(&self.config.date_format)
+                        )
+                        .bright_black()
+                    );
+                } else {
+                    println!("{}", habit.name);
+                }
             }
         }
 
@@ -1,10 +1,59 @@
+use crate::habit::At;
+use chrono::NaiveDate;
 use clap::Args;
 
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 #[clap(verbatim_doc_comment)]
 /// Log a rep for a habit.
+///
+/// By default, the rep is logged now. Use --date, --yesterday and --at
+/// to log a rep you forgot to log.
+///
+/// For measured habits (those with a unit), give the amount done,
+/// e.g. 'habit log reading 25'.
+///
+/// Use --note and --rating to keep track of how the rep went,
+/// e.g. 'habit log run --note "felt great, 5k PR" --rating 5'.
+/// See them with 'habit history'.
 pub struct LogCli {
     #[clap(verbatim_doc_comment)]
     /// The name of the habit for which to log a rep.
     pub habit: String,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(value_parser = clap::value_parser!(u32).range(1..))]
+    /// The amount done, in the unit of the habit.
+    ///
+    /// Required for measured habits, not allowed for the others.
+    pub amount: Option<u32>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, conflicts_with = "yesterday")]
+    /// The date (YYYY-MM-DD) at which the rep was done.
+    ///
+    /// Defaults to today.
+    pub date: Option<NaiveDate>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Log the rep for yesterday.
+    pub yesterday: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The time (hh:mm) at which the rep was done.
+    ///
+    /// Defaults to now if the rep is for today, otherwise to the first
+    /// time set for the habit that day that is not done yet.
+    pub at: Option<At>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// A note about the rep, e.g. how it went.
+    pub note: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, value_parser = clap::value_parser!(u8).range(1..=5))]
+    /// How the rep went, from 1 (bad) to 5 (great).
+    pub rating: Option<u8>,
 }
@@ -1,39 +1,180 @@
+use crate::config::Config;
 use crate::db;
 use crate::engine::Engine;
+use crate::habit::{At, Day};
 use crate::log::cli::LogCli;
+use crate::schedule::Schedule;
+use crate::streak::Streaks;
+use crate::utils;
 use anyhow::anyhow;
+use chrono::{Datelike, Days, Local, NaiveDate};
 use colored::Colorize;
+use std::path::PathBuf;
 
-pub fn get_engine(cli: LogCli) -> Box<dyn Engine> {
-    Box::new(LogEngine { habit: cli.habit })
+pub fn get_engine(cli: LogCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(LogEngine {
+        db_path,
+        config,
+        habit: cli.habit,
+        amount: cli.amount,
+        date: cli.date,
+        yesterday: cli.yesterday,
+        at: cli.at,
+        note: cli
+            .note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty()),
+        rating: cli.rating,
+    })
 }
 
 struct LogEngine {
+    db_path: PathBuf,
+    config: Config,
     habit: String,
+    amount: Option<u32>,
+    date: Option<NaiveDate>,
+    yesterday: bool,
+    at: Option<At>,
+    note: Option<String>,
+    rating: Option<u8>,
 }
 
 impl Engine for LogEngine {
     fn run(&mut self) -> anyhow::Result<()> {
-        let conn = db::open_db()?;
+        let conn = db::open_db(&self.db_path)?;
 
-        // check if habit exists in db, if not error
-        if !db::habit_exists(&conn, &self.habit)? {
-            return Err(anyhow!("Habit '{}' does not exists!", self.habit));
+        // errors if the habit does not exist
+        let habit = db::habit_get_by_name(&conn, &self.habit)?;
+        let value = match (self.amount, &habit.unit) {
+            (Some(amount), Some(_)) => amount,
+            (None, None) => 1,
+            (Some(_), None) => {
+                return Err(anyhow!(
+                    "Habit '{}' is not measured, so reps cannot have an amount. Give it a unit with 'habit edit {} unit'.",
+                    self.habit,
+                    self.habit
+                ))
+            }
+            (None, Some(unit)) => {
+                return Err(anyhow!(
+                    "Habit '{}' is measured, so reps need an amount (in {}), e.g. 'habit log {} 10'.",
+                    self.habit,
+                    unit,
+                    self.habit
+                ))
+            }
+        };
+
+        // find out when the rep was done
+        let now = Local::now();
+        let today = now.date_naive();
+        let date = match self.date {
+            Some(date) => date,
+            None if self.yesterday => today - Days::new(1),
+            None => today,
+        };
+        let mut amount_per_day = db::get_all_amount_per_day_for_habit(&conn, &self.habit)?;
+        let created = match self.at {
+            Some(ref at) => utils::local_datetime(&date, at)?,
+            None if date == today => now,
+            None => {
+                // the first time of the day the habit is not done yet for
+                let times = habit.at.on(&date);
+                let amount = amount_per_day.get(&date).copied().unwrap_or(0);
+                let i = habit.n_times_done_with(amount, &date).min(times.len() - 1);
+                utils::local_datetime(&date, &times[i])?
+            }
+        };
+        if created > now {
+            return Err(anyhow!(
+                "Cannot log a rep in the future ({}).",
+                created.format(&format!("{} %H:%M", self.config.date_format))
+            ));
+        }
+        if let Some(pause) = habit.pause_on(&date) {
+            println!(
+                "{} '{}' is paused until {}.",
+                "Warning:".yellow().bold(),
+                self.habit,
+                pause.until.format(&self.config.date_format)
+            );
+        } else if !habit.is_due_on(&date) {
+            match habit.schedule {
+                Schedule::Days(_) => println!(
+                    "{} '{}' is not scheduled on {}s.",
+                    "Warning:".yellow().bold(),
+                    self.habit,
+                    Day::from(date.weekday())
+                ),
+                _ => println!(
+                    "{} '{}' is not scheduled on {}.",
+                    "Warning:".yellow().bold(),
+                    self.habit,
+                    date.format(&self.config.date_format)
+                ),
+            }
         }
 
         // log a rep
-        db::log_insert(&conn, &self.habit)?;
+        let log_id = db::log_insert_at(
+            &conn,
+            &self.habit,
+            created.timestamp(),
+            value,
+            self.note.as_deref(),
+            self.rating,
+        )?;
 
         // count current number of logged reps for habit
         let n_reps = db::get_n_logs_for_habit(&conn, &self.habit)?;
 
-        println!("Rep successfully logged.");
+        if date == today {
+            println!("Rep successfully logged (log {}).", log_id);
+        } else {
+            println!(
+                "Rep successfully logged for {} {} (log {}).",
+                Day::from(date.weekday()),
+                created.format(&format!("{} %H:%M", self.config.date_format)),
+                log_id
+            );
+        }
         println!(
             "Good job! You are at {} for habit '{}'.",
             format!("{} {}", n_reps, if n_reps <= 1 { "rep" } else { "reps" }).bold(),
             self.habit
         );
 
+        *amount_per_day.entry(date).or_default() += value as usize;
+        if habit.is_measured() || habit.at.on(&date).len() > 1 {
+            let amount = amount_per_day.get(&date).copied().unwrap_or(0);
+            println!(
+                "Done {} of {} that day{}",
+                habit.display_amount(amount).bold(),
+                habit.display_amount(habit.target_on(&date)),
+                if habit.is_done_with(amount, &date) {
+                    ", target reached!"
+                } else {
+                    "."
+                }
+            );
+        }
+
+        // show streaks
+        let streaks = Streaks::compute(&habit, &amount_per_day, today, &self.config.week_start);
+        if let Some(current) = streaks.current {
+            println!(
+                "Current streak: {}.",
+                current.format(&self.config.date_format).bold()
+            );
+        }
+        if let Some(longest) = streaks.longest {
+            println!(
+                "Longest streak: {}.",
+                longest.format(&self.config.date_format)
+            );
+        }
+
         Ok(())
     }
 }
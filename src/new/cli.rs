@@ -1,6 +1,59 @@
+use crate::schedule::Schedule;
+use crate::times::Times;
 use clap::Args;
 
 #[derive(Args, Debug, Clone, PartialEq, Eq)]
 #[clap(verbatim_doc_comment)]
 /// Make a new habit.
-pub struct NewCli {}
+///
+/// You will be prompted for the habit info not given as options,
+/// except for the unit and target which are optional.
+pub struct NewCli {
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The name of the habit (make it short!).
+    pub name: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short = 'D')]
+    /// The description of the habit.
+    pub description: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, visible_alias = "days", short_alias = 'd')]
+    /// When to perform the habit. Either:
+    /// - days of the week, separated by commas. Days can be given
+    ///   by name ('Monday') or by their first three letters ('mon').
+    ///   Use 'daily', 'weekdays' or 'weekends' as shorthands.
+    ///   For example: 'mon,wed,fri' or 'weekdays,sun'.
+    /// - a number of times per week or month, on any days,
+    ///   e.g. '3 per week' or '2 per month'.
+    /// - every few days, e.g. 'every 2 days' (from today)
+    ///   or 'every 2 days from 2024-05-01'.
+    /// - days of the month, e.g. 'monthly on 1,15'.
+    pub schedule: Option<Schedule>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The times (hh:mm) at which to perform the habit, separated by commas.
+    /// Each time is a rep expected that day, e.g. '08:00,20:00' for twice a day.
+    ///
+    /// Times can be for a day of the week only, replacing the others on that day,
+    /// e.g. '07:00,sat 10:00,sun 10:00' for later times on weekends.
+    pub at: Option<Times>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The unit the habit is measured in (e.g. pages, minutes).
+    ///
+    /// Reps of measured habits are logged with an amount,
+    /// e.g. 'habit log reading 25'.
+    pub unit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, value_parser = clap::value_parser!(u32).range(1..))]
+    /// The amount to reach each day for the habit to be done that day.
+    ///
+    /// Defaults to 1, i.e. a single rep.
+    pub target: Option<u32>,
+}
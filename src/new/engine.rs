@@ -1,32 +1,88 @@
 use crate::db;
 use crate::prompt;
+use anyhow::anyhow;
 
 use crate::engine::Engine;
 use crate::habit::Habit;
 use crate::new::cli::NewCli;
+use crate::schedule::Schedule;
+use crate::times::Times;
+use std::path::PathBuf;
 
-pub fn get_engine(cli: NewCli) -> Box<dyn Engine> {
-    let _ = cli;
-    Box::new(NewEngine {})
+pub fn get_engine(cli: NewCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(NewEngine {
+        db_path,
+        name: cli.name,
+        description: cli.description,
+        schedule: cli.schedule,
+        at: cli.at,
+        unit: cli.unit,
+        target: cli.target,
+    })
 }
 
-struct NewEngine {}
+struct NewEngine {
+    db_path: PathBuf,
+    name: Option<String>,
+    description: Option<String>,
+    schedule: Option<Schedule>,
+    at: Option<Times>,
+    unit: Option<String>,
+    target: Option<u32>,
+}
 
 impl Engine for NewEngine {
     fn run(&mut self) -> anyhow::Result<()> {
-        // ask habit info
-        let name = prompt::prompt_habit_name()?;
-        let description = prompt::prompt_habit_description()?;
-        let days = prompt::prompt_habit_days()?;
-        let at = prompt::prompt_habit_at()?;
-        let habit = Habit::new(name, description, days, at);
+        let conn = db::open_db(&self.db_path)?;
+
+        // get habit info from options, ask for the missing ones
+        let name = match self.name {
+            Some(ref name) => {
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(anyhow!("Name cannot be empty!"));
+                }
+                if db::habit_exists(&conn, name)? {
+                    return Err(anyhow!("Habit '{}' already exists!", name));
+                }
+                name.to_string()
+            }
+            None => prompt::prompt_habit_name(&conn)?,
+        };
+        let description = match self.description {
+            Some(ref description) => description.trim().to_string(),
+            None => prompt::prompt_habit_description()?,
+        };
+        let schedule = match self.schedule {
+            Some(ref schedule) => schedule.clone(),
+            None => prompt::prompt_habit_schedule()?,
+        };
+        let at = match self.at {
+            Some(ref at) => at.clone(),
+            None => prompt::prompt_habit_at(None)?,
+        };
+        let unit = match self.unit {
+            Some(ref unit) if unit.trim().is_empty() => {
+                return Err(anyhow!("Unit cannot be empty!"));
+            }
+            Some(ref unit) => Some(unit.trim().to_string()),
+            None => None,
+        };
+        let habit = Habit {
+            unit,
+            target: self.target,
+            ..Habit::new(name, description, schedule, at)
+        };
 
         // add to DB
-        let conn = db::open_db()?;
         db::habit_insert(&conn, &habit)?;
 
         println!("Habit '{}' successfully created!", habit.name);
-        println!("Run 'habit log {}' to log progress.", habit.name);
+        if habit.is_measured() {
+            println!("Run 'habit log {} <amount>' to log progress.", habit.name);
+        } else {
+            println!("Run 'habit log {}' to log progress.", habit.name);
+        }
         println!("Run 'habit show {}' to show progress.", habit.name);
 
         Ok(())
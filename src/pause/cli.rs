@@ -0,0 +1,29 @@
+use chrono::NaiveDate;
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Pause a habit for a while, e.g. during a vacation.
+///
+/// The habit is not scheduled while paused, so that it does not break streaks,
+/// and is hidden from 'habit list', 'habit today' and 'habit show'.
+/// Pausing a habit again replaces the pauses overlapping the new one,
+/// and keeps the others.
+/// Use 'habit resume' to end a pause early.
+pub struct PauseCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to pause.
+    pub habit: String,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The last date (YYYY-MM-DD) of the pause.
+    pub until: NaiveDate,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The first date (YYYY-MM-DD) of the pause.
+    ///
+    /// Defaults to today.
+    pub from: Option<NaiveDate>,
+}
@@ -0,0 +1,55 @@
+use crate::config::Config;
+use crate::db;
+use crate::engine::Engine;
+use crate::pause::cli::PauseCli;
+use anyhow::anyhow;
+use chrono::{Local, NaiveDate};
+use std::path::PathBuf;
+
+pub fn get_engine(cli: PauseCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(PauseEngine {
+        db_path,
+        config,
+        habit: cli.habit,
+        until: cli.until,
+        from: cli.from,
+    })
+}
+
+struct PauseEngine {
+    db_path: PathBuf,
+    config: Config,
+    habit: String,
+    until: NaiveDate,
+    from: Option<NaiveDate>,
+}
+
+impl Engine for PauseEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // errors if the habit does not exist
+        let mut habit = db::habit_get_by_name(&conn, &self.habit)?;
+
+        let from = self.from.unwrap_or(Local::now().date_naive());
+        if self.until < from {
+            return Err(anyhow!(
+                "Cannot pause until {}, which is before {}.",
+                self.until.format(&self.config.date_format),
+                from.format(&self.config.date_format)
+            ));
+        }
+
+        habit.pause(from, self.until);
+        db::habit_update_pauses(&conn, &self.habit, &habit.pauses)?;
+
+        println!(
+            "Habit '{}' successfully paused from {} until {}!",
+            self.habit,
+            from.format(&self.config.date_format),
+            self.until.format(&self.config.date_format)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+mod cli;
+mod engine;
+mod store;
+
+pub use cli::ProfileCli;
+pub use engine::get_engine;
+pub use store::{
+    current_profile, list_profiles, profile_db_path, profile_exists, set_current_profile,
+    validate_profile_name, DEFAULT_PROFILE,
+};
@@ -0,0 +1,41 @@
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Manage profiles.
+///
+/// Each profile has its own habits and logs. Use --profile to pick
+/// the profile of a command, or switch the default one.
+pub struct ProfileCli {
+    #[command(subcommand)]
+    pub command: ProfileCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum ProfileCommand {
+    /// List existing profiles.
+    List,
+    /// Make a new profile.
+    Create {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the profile to make.
+        name: String,
+    },
+    /// Use a profile when none is given with --profile.
+    Switch {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the profile to use.
+        name: String,
+    },
+    /// Delete a profile, along with all its habits and logs.
+    Delete {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the profile to delete.
+        name: String,
+
+        #[clap(verbatim_doc_comment)]
+        #[clap(long, short, action)]
+        /// Do not ask for confirmation.
+        yes: bool,
+    },
+}
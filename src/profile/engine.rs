@@ -0,0 +1,98 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::profile::cli::{ProfileCli, ProfileCommand};
+use crate::profile::{
+    current_profile, list_profiles, profile_db_path, profile_exists, set_current_profile,
+    validate_profile_name, DEFAULT_PROFILE,
+};
+use crate::prompt;
+use anyhow::{anyhow, Context};
+use colored::Colorize;
+use std::fs;
+
+pub fn get_engine(cli: ProfileCli) -> Box<dyn Engine> {
+    Box::new(ProfileEngine {
+        command: cli.command,
+    })
+}
+
+struct ProfileEngine {
+    command: ProfileCommand,
+}
+
+impl Engine for ProfileEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        match self.command {
+            ProfileCommand::List => {
+                let current = current_profile()?;
+                for name in list_profiles()? {
+                    if name == current {
+                        println!("{} {}", "*".bold(), name.bold());
+                    } else {
+                        println!("  {}", name);
+                    }
+                }
+                Ok(())
+            }
+            ProfileCommand::Create { ref name } => {
+                validate_profile_name(name)?;
+                if profile_exists(name) {
+                    return Err(anyhow!("Profile '{}' already exists!", name));
+                }
+
+                let db_path = profile_db_path(name);
+                if let Some(db_dir) = db_path.parent() {
+                    fs::create_dir_all(db_dir)?;
+                }
+                let mut conn = db::open_db(&db_path)?;
+                db::migrate(&mut conn)?;
+
+                println!("Profile '{}' successfully created!", name);
+                println!("Run 'habit profile switch {}' to use it by default.", name);
+                Ok(())
+            }
+            ProfileCommand::Switch { ref name } => {
+                validate_profile_name(name)?;
+                if !profile_exists(name) {
+                    return Err(anyhow!("Profile '{}' does not exists!", name));
+                }
+
+                set_current_profile(name)?;
+                println!("Now using profile '{}'.", name);
+                Ok(())
+            }
+            ProfileCommand::Delete { ref name, yes } => {
+                validate_profile_name(name)?;
+                if name == DEFAULT_PROFILE {
+                    return Err(anyhow!("The default profile cannot be deleted."));
+                }
+                if !profile_exists(name) {
+                    return Err(anyhow!("Profile '{}' does not exists!", name));
+                }
+
+                // ask for confirmation
+                let confirmed = yes
+                    || prompt::ask_for_confirmation(&format!(
+                        "Are you sure? All habits and logs of profile '{}' will be lost.",
+                        name
+                    ))?;
+
+                // delete profile
+                if confirmed {
+                    let db_path = profile_db_path(name);
+                    fs::remove_file(&db_path).with_context(|| {
+                        format!("Failed to delete {}.", db_path.to_string_lossy())
+                    })?;
+                    if current_profile()? == *name {
+                        set_current_profile(DEFAULT_PROFILE)?;
+                        println!("Now using profile '{}'.", DEFAULT_PROFILE);
+                    }
+                    println!("Profile '{}' successfully deleted!", name);
+                } else {
+                    println!("Nothing done.");
+                }
+                Ok(())
+            }
+        }
+    }
+}
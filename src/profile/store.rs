@@ -0,0 +1,95 @@
+use crate::{DATA_DIR, DB_PATH};
+use anyhow::{anyhow, Context};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fs;
+use std::path::PathBuf;
+
+lazy_static! {
+    static ref PROFILE_NAME_RE: Regex = Regex::new(r"^[A-Za-z0-9_-]+$").unwrap();
+    /// Directory of the databases of profiles other than the default one.
+    static ref PROFILES_DIR: PathBuf = DATA_DIR.join("profiles");
+    /// File storing the name of the profile to use when none is given.
+    static ref CURRENT_PROFILE_PATH: PathBuf = DATA_DIR.join("current_profile");
+}
+
+/// The profile whose database is the one habit used before profiles existed.
+pub const DEFAULT_PROFILE: &str = "default";
+
+pub fn validate_profile_name(name: &str) -> anyhow::Result<()> {
+    if PROFILE_NAME_RE.is_match(name) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid profile name '{}'. Use only letters, digits, '-' and '_'.",
+            name
+        ))
+    }
+}
+
+/// The database file of profile `name`.
+pub fn profile_db_path(name: &str) -> PathBuf {
+    if name == DEFAULT_PROFILE {
+        DB_PATH.clone()
+    } else {
+        PROFILES_DIR.join(format!("{}.db", name))
+    }
+}
+
+pub fn profile_exists(name: &str) -> bool {
+    name == DEFAULT_PROFILE || profile_db_path(name).exists()
+}
+
+/// All profiles, the default one first, then by name.
+pub fn list_profiles() -> anyhow::Result<Vec<String>> {
+    let mut profiles = Vec::new();
+    if PROFILES_DIR.exists() {
+        let entries = fs::read_dir(PROFILES_DIR.as_path()).with_context(|| {
+            format!(
+                "Failed to read profiles directory {}.",
+                PROFILES_DIR.to_string_lossy()
+            )
+        })?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "db") {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    profiles.push(name.to_string());
+                }
+            }
+        }
+    }
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+
+    Ok(profiles)
+}
+
+/// The profile to use when none is given, as set by 'habit profile switch'.
+pub fn current_profile() -> anyhow::Result<String> {
+    if !CURRENT_PROFILE_PATH.exists() {
+        return Ok(DEFAULT_PROFILE.to_string());
+    }
+
+    let name = fs::read_to_string(CURRENT_PROFILE_PATH.as_path())
+        .with_context(|| {
+            format!(
+                "Failed to read current profile from {}.",
+                CURRENT_PROFILE_PATH.to_string_lossy()
+            )
+        })?
+        .trim()
+        .to_string();
+
+    Ok(name)
+}
+
+pub fn set_current_profile(name: &str) -> anyhow::Result<()> {
+    fs::create_dir_all(DATA_DIR.as_path())?;
+    fs::write(CURRENT_PROFILE_PATH.as_path(), name).with_context(|| {
+        format!(
+            "Failed to write current profile to {}.",
+            CURRENT_PROFILE_PATH.to_string_lossy()
+        )
+    })
+}
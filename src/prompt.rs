@@ -1,28 +1,17 @@
 use crate::db;
-use crate::habit::At;
-use crate::habit::Day;
-use crate::habit::ParseAtError;
+use crate::habit::Habit;
+use crate::habit::Log;
+use crate::schedule::{ParseScheduleError, Schedule};
+use crate::times::{ParseTimesError, Times};
 use anyhow::Context;
 use dialoguer::Confirm;
 use dialoguer::MultiSelect;
+use dialoguer::Select;
 use dialoguer::{theme::ColorfulTheme, Input};
-use lazy_static::lazy_static;
+use rusqlite::Connection;
 use std::str::FromStr;
 
-lazy_static! {
-    static ref DAYS: [Day; 7] = [
-        Day::Monday,
-        Day::Tuesday,
-        Day::Wednesday,
-        Day::Thursday,
-        Day::Friday,
-        Day::Saturday,
-        Day::Sunday,
-    ];
-}
-
-pub fn prompt_habit_name() -> anyhow::Result<String> {
-    let conn = db::open_db()?;
+pub fn prompt_habit_name(conn: &Connection) -> anyhow::Result<String> {
     let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
 
     let name = Input::<String>::with_theme(&dialoguer_theme)
@@ -30,17 +19,10 @@ pub fn prompt_habit_name() -> anyhow::Result<String> {
         .validate_with(|input: &String| -> Result<(), String> {
             // Check that there is no existing habit with the same name
             let input = input.trim();
-            match conn.query_row(
-                "SELECT name FROM habit WHERE name = ?1",
-                rusqlite::params![input],
-                |_| Ok(()),
-            ) {
-                Ok(_) => Err(format!("Habit '{}' already exists!", input)),
-                Err(rusqlite::Error::QueryReturnedNoRows) => Ok(()),
-                Err(e) => Err(format!(
-                    "Query to select habit with name '{}' failed.\n{}",
-                    input, e
-                )),
+            match db::habit_exists(conn, input) {
+                Ok(true) => Err(format!("Habit '{}' already exists!", input)),
+                Ok(false) => Ok(()),
+                Err(e) => Err(e.to_string()),
             }
         })
         .interact_text()?
@@ -62,33 +44,130 @@ pub fn prompt_habit_description() -> anyhow::Result<String> {
     Ok(description)
 }
 
-pub fn prompt_habit_days() -> anyhow::Result<Vec<Day>> {
+pub fn prompt_habit_schedule() -> anyhow::Result<Schedule> {
+    let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
+
+    Schedule::from_str(
+        Input::<String>::with_theme(&dialoguer_theme)
+            .with_prompt("Schedule (e.g. 'mon,wed,fri', 'daily', '3 per week', 'every 2 days', 'monthly on 1,15')")
+            .validate_with(|input: &String| -> Result<(), ParseScheduleError> {
+                Schedule::from_str(input).map(|_| ())
+            })
+            .interact_text()?
+            .trim(),
+    )
+    .with_context(|| "Not possible if validate_with worked correctly.")
+}
+
+/// Ask for the times of a habit, starting from the `current` ones if any
+/// so that they can be added to or removed from.
+pub fn prompt_habit_at(current: Option<&Times>) -> anyhow::Result<Times> {
+    let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
+
+    let mut input = Input::<String>::with_theme(&dialoguer_theme)
+        .with_prompt("At (hh:mm, separated by commas)")
+        .validate_with(|input: &String| -> Result<(), ParseTimesError> {
+            Times::from_str(input).map(|_| ())
+        });
+    if let Some(current) = current {
+        input = input.with_initial_text(current.to_string());
+    }
+
+    Times::from_str(input.interact_text()?.trim())
+        .with_context(|| "Not possible if validate_with worked correctly.")
+}
+
+/// Ask for the unit of a habit, if it is to be measured.
+pub fn prompt_habit_unit() -> anyhow::Result<Option<String>> {
+    let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
+
+    let unit = Input::<String>::with_theme(&dialoguer_theme)
+        .with_prompt("Unit (e.g. pages, leave empty if not measured)")
+        .allow_empty(true)
+        .interact_text()?
+        .trim()
+        .to_string();
+
+    Ok(if unit.is_empty() { None } else { Some(unit) })
+}
+
+/// Ask for the daily target of a habit, if any.
+pub fn prompt_habit_target() -> anyhow::Result<Option<u32>> {
     let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
 
-    let days = MultiSelect::with_theme(&dialoguer_theme)
-        .with_prompt("Days")
-        .items(&DAYS[..])
+    let target = Input::<String>::with_theme(&dialoguer_theme)
+        .with_prompt("Daily target (leave empty for a single rep)")
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.trim() {
+                "" => Ok(()),
+                input => match u32::from_str(input) {
+                    Ok(target) if target > 0 => Ok(()),
+                    _ => Err("The target must be a positive integer."),
+                },
+            }
+        })
+        .interact_text()?;
+
+    match target.trim() {
+        "" => Ok(None),
+        target => Ok(Some(u32::from_str(target).with_context(|| {
+            "Not possible if validate_with worked correctly."
+        })?)),
+    }
+}
+
+/// Ask for the amount done for the measured `habit`.
+pub fn prompt_amount(habit: &Habit) -> anyhow::Result<u32> {
+    let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
+
+    let amount = Input::<u32>::with_theme(&dialoguer_theme)
+        .with_prompt(format!(
+            "Amount for '{}' (in {})",
+            habit.name,
+            habit.unit.as_deref().unwrap_or("reps")
+        ))
+        .validate_with(|input: &u32| -> Result<(), &str> {
+            if *input > 0 {
+                Ok(())
+            } else {
+                Err("The amount must be positive.")
+            }
+        })
+        .interact_text()?;
+
+    Ok(amount)
+}
+
+/// Pick any number of `habits`.
+pub fn prompt_habits(prompt_mess: &str, habits: &[Habit]) -> anyhow::Result<Vec<Habit>> {
+    let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
+
+    let items: Vec<String> = habits
+        .iter()
+        .map(|habit| format!("{}  {}", habit.at, habit.name))
+        .collect();
+    let selected = MultiSelect::with_theme(&dialoguer_theme)
+        .with_prompt(prompt_mess)
+        .items(&items)
         .interact()?
         .into_iter()
-        .map(|i| DAYS[i].clone())
+        .map(|i| habits[i].clone())
         .collect();
 
-    Ok(days)
+    Ok(selected)
 }
 
-pub fn prompt_habit_at() -> anyhow::Result<At> {
+pub fn prompt_log(logs: &[Log]) -> anyhow::Result<Log> {
     let dialoguer_theme: ColorfulTheme = ColorfulTheme::default();
 
-    At::from_str(
-        Input::<String>::with_theme(&dialoguer_theme)
-            .with_prompt("At (hh:mm)")
-            .validate_with(|input: &String| -> Result<(), ParseAtError> {
-                At::from_str(input).map(|_| ())
-            })
-            .interact_text()?
-            .trim(),
-    )
-    .with_context(|| "Not possible if validate_with worked correctly.")
+    let i = Select::with_theme(&dialoguer_theme)
+        .with_prompt("Log")
+        .items(logs)
+        .default(0)
+        .interact()?;
+
+    Ok(logs[i].clone())
 }
 
 pub fn ask_for_confirmation(prompt_mess: &str) -> anyhow::Result<bool> {
@@ -0,0 +1,6 @@
+mod cli;
+mod engine;
+mod notifier;
+
+pub use cli::RemindCli;
+pub use engine::get_engine;
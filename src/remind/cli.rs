@@ -0,0 +1,46 @@
+use crate::config::Notifier;
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Remind of habits when their time comes.
+///
+/// Runs until stopped, sending a notification whenever one of the times of
+/// a habit scheduled today passes without the habit being done for it.
+/// Use --check to check once and exit instead, e.g. from cron or a systemd timer.
+///
+/// Notifications are sent with the notifier set in the config (see 'habit config'),
+/// except during the quiet hours set in the config.
+pub struct RemindCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to remind of.
+    ///
+    /// Defaults to all habits.
+    pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Check once for habits to remind of, then exit.
+    pub check: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(
+        long,
+        short,
+        value_name = "MINUTES",
+        requires = "habit",
+        conflicts_with = "check"
+    )]
+    #[clap(value_parser = clap::value_parser!(u32).range(1..))]
+    /// Snooze the reminders of the habit for MINUTES minutes, then exit.
+    ///
+    /// Once snoozed, the habit is reminded of again when the time is up.
+    pub snooze: Option<u32>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// How to send notifications.
+    ///
+    /// Defaults to the notifier set in the config.
+    pub notifier: Option<Notifier>,
+}
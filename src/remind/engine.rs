@@ -0,0 +1,169 @@
+use crate::config::Config;
+use crate::db;
+use crate::engine::Engine;
+use crate::remind::cli::RemindCli;
+use crate::remind::notifier::{self, Notify, Reminder};
+use chrono::{DateTime, Duration, Local, NaiveDate, Timelike};
+use colored::Colorize;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::{thread, time};
+
+pub fn get_engine(cli: RemindCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(RemindEngine {
+        db_path,
+        notifier: notifier::get_notifier(cli.notifier.unwrap_or(config.notifier), &config),
+        config,
+        habit: cli.habit,
+        check: cli.check,
+        snooze: cli.snooze,
+    })
+}
+
+struct RemindEngine {
+    db_path: PathBuf,
+    config: Config,
+    notifier: Box<dyn Notify>,
+    habit: Option<String>,
+    check: bool,
+    snooze: Option<u32>,
+}
+
+impl Engine for RemindEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // errors if the habit does not exist
+        if let Some(ref habit) = self.habit {
+            db::habit_get_by_name(&conn, habit)?;
+        }
+
+        if let Some(minutes) = self.snooze {
+            let habit = self
+                .habit
+                .as_ref()
+                .expect("A habit should be given to snooze.");
+            let until = Local::now() + Duration::minutes(minutes.into());
+            db::snooze_set(&conn, habit, until.timestamp())?;
+            println!(
+                "Reminders of '{}' successfully snoozed until {}.",
+                habit,
+                until.format("%H:%M")
+            );
+            return Ok(());
+        }
+
+        if self.check {
+            let now = Local::now();
+            if !self.config.is_quiet_at(now.time()) {
+                for (reminder, _, _) in self.due_reminders(&conn, &now)? {
+                    self.notifier.notify(&reminder)?;
+                }
+            }
+            return Ok(());
+        }
+
+        // When each reminder was sent, by habit, day and index of the time in the day.
+        let mut sent: HashMap<(String, NaiveDate, usize), i64> = HashMap::new();
+        loop {
+            let now = Local::now();
+            let today = now.date_naive();
+            sent.retain(|(_, day, _), _| *day == today);
+
+            // reminders due during quiet hours are sent once they are over
+            if !self.config.is_quiet_at(now.time()) {
+                // errors are only reported, not to stop reminding
+                match self.due_reminders(&conn, &now) {
+                    Ok(reminders) => {
+                        for (reminder, i, snoozed_until) in reminders {
+                            let key = (reminder.habit.clone(), today, i);
+                            // remind again once a snooze made after the last reminder is over
+                            let already_sent = sent.get(&key).is_some_and(|sent_at| {
+                                snoozed_until.is_none_or(|until| until <= *sent_at)
+                            });
+                            if already_sent {
+                                continue;
+                            }
+                            // a failed reminder is not tried again, not to report it every minute
+                            if let Err(e) = self.notifier.notify(&reminder) {
+                                eprintln!(
+                                    "{} Failed to remind of habit '{}': {:#}",
+                                    "Error:".red().bold(),
+                                    reminder.habit,
+                                    e
+                                );
+                            }
+                            sent.insert(key, now.timestamp());
+                        }
+                    }
+                    Err(e) => eprintln!(
+                        "{} Failed to check for reminders: {:#}",
+                        "Error:".red().bold(),
+                        e
+                    ),
+                }
+            }
+
+            // check again at the start of the next minute
+            thread::sleep(time::Duration::from_secs(60 - now.second() as u64));
+        }
+    }
+}
+
+impl RemindEngine {
+    /// The reminders due at `now`: for each habit scheduled today, the last of its times
+    /// that has passed if the habit is not done for it yet, unless it is snoozed.
+    /// Each comes with the index of the time in the day, and the timestamp the habit
+    /// was last snoozed until if any.
+    fn due_reminders(
+        &self,
+        conn: &Connection,
+        now: &DateTime<Local>,
+    ) -> anyhow::Result<Vec<(Reminder, usize, Option<i64>)>> {
+        let today = now.date_naive();
+        let habits = match self.habit {
+            Some(ref habit_name) => vec![db::habit_get_by_name(conn, habit_name)?],
+            None => db::habit_get_all(conn)?,
+        };
+
+        let mut reminders = Vec::new();
+        for habit in habits {
+            if habit.archived || !habit.is_due_on(&today) {
+                continue;
+            }
+            let amount_per_day = db::get_all_amount_per_day_for_habit(conn, &habit.name)?;
+            if let Some((period, n_done)) =
+                habit.period_progress(&amount_per_day, &today, &self.config.week_start)
+            {
+                if n_done >= period.n as usize {
+                    continue;
+                }
+            }
+
+            let times = habit.at.on(&today);
+            let n_passed = times.iter().filter(|at| at.time() <= now.time()).count();
+            let amount = amount_per_day.get(&today).copied().unwrap_or(0);
+            if n_passed <= habit.n_times_done_with(amount, &today) {
+                continue;
+            }
+
+            let snoozed_until = db::snooze_get(conn, &habit.name)?;
+            if snoozed_until.is_some_and(|until| until > now.timestamp()) {
+                continue;
+            }
+
+            reminders.push((
+                Reminder {
+                    habit: habit.name,
+                    description: habit.description,
+                    at: times[n_passed - 1].clone(),
+                },
+                n_passed - 1,
+                snoozed_until,
+            ));
+        }
+
+        Ok(reminders)
+    }
+}
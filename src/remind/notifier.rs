@@ -0,0 +1,84 @@
+use crate::config::{Config, Notifier};
+use crate::habit::At;
+use anyhow::{anyhow, Context};
+use std::process::Command;
+
+/// A reminder that the time has come to perform a habit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    pub habit: String,
+    pub description: String,
+    /// The time of the habit that has passed.
+    pub at: At,
+}
+
+pub trait Notify {
+    fn notify(&self, reminder: &Reminder) -> anyhow::Result<()>;
+}
+
+pub fn get_notifier(notifier: Notifier, config: &Config) -> Box<dyn Notify> {
+    match notifier {
+        Notifier::Stdout => Box::new(StdoutNotifier),
+        Notifier::NotifySend => Box::new(NotifySendNotifier),
+        Notifier::Script => Box::new(ScriptNotifier {
+            script: config.notifier_script.clone(),
+        }),
+    }
+}
+
+struct StdoutNotifier;
+
+impl Notify for StdoutNotifier {
+    fn notify(&self, reminder: &Reminder) -> anyhow::Result<()> {
+        println!("{}  Time for '{}'!", reminder.at, reminder.habit);
+        Ok(())
+    }
+}
+
+struct NotifySendNotifier;
+
+impl Notify for NotifySendNotifier {
+    fn notify(&self, reminder: &Reminder) -> anyhow::Result<()> {
+        let status = Command::new("notify-send")
+            .arg("--app-name=habit")
+            .arg(format!("{}  Time for '{}'!", reminder.at, reminder.habit))
+            .arg(&reminder.description)
+            .status()
+            .with_context(|| "Failed to run notify-send. Is it installed?")?;
+        if !status.success() {
+            return Err(anyhow!("notify-send failed ({}).", status));
+        }
+
+        Ok(())
+    }
+}
+
+struct ScriptNotifier {
+    script: String,
+}
+
+impl Notify for ScriptNotifier {
+    fn notify(&self, reminder: &Reminder) -> anyhow::Result<()> {
+        if self.script.is_empty() {
+            return Err(anyhow!(
+                "No script to notify with. Set one with 'habit config set notifier_script <path>'."
+            ));
+        }
+
+        let status = Command::new(&self.script)
+            .arg(&reminder.habit)
+            .arg(reminder.at.to_string())
+            .arg(&reminder.description)
+            .status()
+            .with_context(|| format!("Failed to run notifier script {}.", self.script))?;
+        if !status.success() {
+            return Err(anyhow!(
+                "Notifier script {} failed ({}).",
+                self.script,
+                status
+            ));
+        }
+
+        Ok(())
+    }
+}
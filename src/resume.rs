@@ -0,0 +1,5 @@
+mod cli;
+mod engine;
+
+pub use cli::ResumeCli;
+pub use engine::get_engine;
@@ -0,0 +1,12 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Resume a paused habit from today on.
+///
+/// Ends the current pause, and cancels the pauses to come.
+pub struct ResumeCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to resume.
+    pub habit: String,
+}
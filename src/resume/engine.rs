@@ -0,0 +1,36 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::resume::cli::ResumeCli;
+use anyhow::anyhow;
+use chrono::Local;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: ResumeCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(ResumeEngine {
+        db_path,
+        habit: cli.habit,
+    })
+}
+
+struct ResumeEngine {
+    db_path: PathBuf,
+    habit: String,
+}
+
+impl Engine for ResumeEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // errors if the habit does not exist
+        let mut habit = db::habit_get_by_name(&conn, &self.habit)?;
+
+        if !habit.end_pauses(Local::now().date_naive()) {
+            return Err(anyhow!("Habit '{}' is not paused!", self.habit));
+        }
+        db::habit_update_pauses(&conn, &self.habit, &habit.pauses)?;
+
+        println!("Habit '{}' successfully resumed!", self.habit);
+
+        Ok(())
+    }
+}
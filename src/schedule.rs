@@ -0,0 +1,393 @@
+use crate::habit::{self, Day};
+use crate::utils;
+use chrono::{Datelike, Days, Local, Months, NaiveDate, Weekday};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+lazy_static! {
+    static ref PER_PERIOD_RE: Regex =
+        Regex::new(r"^(?<n>\d+)\s+(?:times?\s+)?(?:per|a)\s+(?<period>week|month)$").unwrap();
+    static ref EVERY_N_DAYS_RE: Regex =
+        Regex::new(r"^every\s+(?:(?<n>\d+)\s+)?days?(?:\s+from\s+(?<start>\d{4}-\d{2}-\d{2}))?$")
+            .unwrap();
+    static ref DAYS_OF_MONTH_RE: Regex =
+        Regex::new(r"^monthly\s+on\s+(?<days>\d+(?:\s*,\s*\d+)*)$").unwrap();
+}
+
+/// When a habit is to be performed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// On fixed days of the week.
+    Days(Vec<Day>),
+    /// A number of times per week, on any days.
+    TimesPerWeek(u32),
+    /// A number of times per month, on any days.
+    TimesPerMonth(u32),
+    /// Every `n` days, starting on `start`.
+    EveryNDays { n: u32, start: NaiveDate },
+    /// On fixed days of the month (1 to 31).
+    /// Days after the end of a month are understood as its last day.
+    DaysOfMonth(Vec<u32>),
+}
+
+impl Schedule {
+    /// Whether the habit can be performed on `date` as scheduled.
+    /// Habits to perform a number of times per week or month can be performed on any day.
+    pub fn is_due_on(&self, date: &NaiveDate) -> bool {
+        match self {
+            Schedule::Days(days) => days.contains(&Day::from(date.weekday())),
+            Schedule::TimesPerWeek(_) | Schedule::TimesPerMonth(_) => true,
+            Schedule::EveryNDays { n, start } => {
+                *date >= *start && (*date - *start).num_days() % *n as i64 == 0
+            }
+            Schedule::DaysOfMonth(days) => {
+                let last_day = last_day_of_month(date).day();
+                days.iter().any(|day| (*day).min(last_day) == date.day())
+            }
+        }
+    }
+
+    /// For habits to perform a number of times per period, the first and last days
+    /// of the period containing `date` and the number of times, `None` for other habits.
+    /// Weeks start on `week_start`.
+    pub fn period_of(&self, date: &NaiveDate, week_start: &Day) -> Option<Period> {
+        match self {
+            Schedule::TimesPerWeek(n) => {
+                let first = *date
+                    - Days::new(date.weekday().days_since(Weekday::from(week_start.clone())) as u64);
+                Some(Period {
+                    first,
+                    last: first + Days::new(6),
+                    n: *n,
+                })
+            }
+            Schedule::TimesPerMonth(n) => Some(Period {
+                first: date.with_day(1).expect("The 1st of a month should exist."),
+                last: last_day_of_month(date),
+                n: *n,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Describe the schedule in plain words, e.g. "Each Monday and Friday".
+    pub fn describe(&self) -> String {
+        match self {
+            Schedule::Days(days) => format!("Each {}", utils::display_days(days)),
+            Schedule::TimesPerWeek(n) => format!("{} a week", display_times(*n)),
+            Schedule::TimesPerMonth(n) => format!("{} a month", display_times(*n)),
+            Schedule::EveryNDays { n: 1, start } => format!("Every day from {}", start),
+            Schedule::EveryNDays { n, start } => format!("Every {} days from {}", n, start),
+            Schedule::DaysOfMonth(days) => format!(
+                "On {} {} of each month",
+                if days.len() <= 1 { "day" } else { "days" },
+                utils::display_list(&days.iter().map(|day| day.to_string()).collect::<Vec<_>>())
+            ),
+        }
+    }
+}
+
+/// A period in which a habit is to be performed a number of times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Period {
+    pub first: NaiveDate,
+    pub last: NaiveDate,
+    /// Number of days the habit is to be performed on in the period.
+    pub n: u32,
+}
+
+impl Period {
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.first.iter_days().take_while(|day| *day <= self.last)
+    }
+}
+
+fn last_day_of_month(date: &NaiveDate) -> NaiveDate {
+    let first = date.with_day(1).expect("The 1st of a month should exist.");
+    first + Months::new(1) - Days::new(1)
+}
+
+fn display_times(n: u32) -> String {
+    match n {
+        1 => String::from("Once"),
+        2 => String::from("Twice"),
+        n => format!("{} times", n),
+    }
+}
+
+/// The form the schedule is input in and stored as.
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Days(days) => write!(
+                f,
+                "{}",
+                days.iter()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            Schedule::TimesPerWeek(n) => write!(f, "{} per week", n),
+            Schedule::TimesPerMonth(n) => write!(f, "{} per month", n),
+            Schedule::EveryNDays { n, start } => {
+                write!(f, "every {} days from {}", n, start.format("%Y-%m-%d"))
+            }
+            Schedule::DaysOfMonth(days) => write!(
+                f,
+                "monthly on {}",
+                days.iter()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseScheduleError {
+    Unknown,
+    TimesOutOfRange,
+    EveryZeroDays,
+    DayOfMonthOutOfRange,
+}
+
+impl fmt::Display for ParseScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => write!(
+                f,
+                "Unknown schedule. Should be days like 'mon,wed,fri' or 'weekdays', '3 per week', '2 per month', 'every 3 days' (optionally 'from YYYY-MM-DD') or 'monthly on 1,15'."
+            ),
+            Self::TimesOutOfRange => write!(
+                f,
+                "Number of times out of range. Must be in [[1, 7]] per week, [[1, 28]] per month."
+            ),
+            Self::EveryZeroDays => write!(f, "Number of days must be at least 1."),
+            Self::DayOfMonthOutOfRange => {
+                write!(f, "Day of month out of range. Must be in [[1, 31]].")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseScheduleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        self.source()
+    }
+}
+
+/// Parses schedules as displayed, ignoring case. Schedules can be:
+/// - days of the week, see habit::parse_days ("mon,wed,fri", "weekdays")
+/// - a number of times per week or month ("3 per week", "2 times per month")
+/// - every n days, starting today unless given ("every 2 days from 2024-05-01")
+/// - days of the month ("monthly on 1,15")
+impl FromStr for Schedule {
+    type Err = ParseScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, ParseScheduleError> {
+        let s = s.trim().to_lowercase();
+
+        if let Some(caps) = PER_PERIOD_RE.captures(&s) {
+            let n = u32::from_str(&caps["n"]).map_err(|_| ParseScheduleError::TimesOutOfRange)?;
+            return match &caps["period"] {
+                "week" if (1..=7).contains(&n) => Ok(Schedule::TimesPerWeek(n)),
+                "month" if (1..=28).contains(&n) => Ok(Schedule::TimesPerMonth(n)),
+                _ => Err(ParseScheduleError::TimesOutOfRange),
+            };
+        }
+
+        if let Some(caps) = EVERY_N_DAYS_RE.captures(&s) {
+            let n = match caps.name("n") {
+                Some(n) => u32::from_str(n.as_str()).map_err(|_| ParseScheduleError::Unknown)?,
+                None => 1,
+            };
+            if n == 0 {
+                return Err(ParseScheduleError::EveryZeroDays);
+            }
+            let start = match caps.name("start") {
+                Some(start) => NaiveDate::parse_from_str(start.as_str(), "%Y-%m-%d")
+                    .map_err(|_| ParseScheduleError::Unknown)?,
+                None => Local::now().date_naive(),
+            };
+            return Ok(Schedule::EveryNDays { n, start });
+        }
+
+        if let Some(caps) = DAYS_OF_MONTH_RE.captures(&s) {
+            let mut days = Vec::new();
+            for day in caps["days"].split(',') {
+                match u32::from_str(day.trim()) {
+                    Ok(day) if (1..=31).contains(&day) => days.push(day),
+                    _ => return Err(ParseScheduleError::DayOfMonthOutOfRange),
+                }
+            }
+            days.sort();
+            days.dedup();
+            return Ok(Schedule::DaysOfMonth(days));
+        }
+
+        habit::parse_days(&s)
+            .map(Schedule::Days)
+            .map_err(|_| ParseScheduleError::Unknown)
+    }
+}
+
+// Serialized as a string, like it is input.
+impl Serialize for Schedule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Habits used to only have days of the week, serialized as a list.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScheduleRepr {
+    Schedule(String),
+    Days(Vec<Day>),
+}
+
+impl<'de> Deserialize<'de> for Schedule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match ScheduleRepr::deserialize(deserializer)? {
+            ScheduleRepr::Schedule(s) => Schedule::from_str(&s).map_err(serde::de::Error::custom),
+            ScheduleRepr::Days(mut days) => {
+                days.sort();
+                days.dedup();
+                Ok(Schedule::Days(days))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parse_display_round_trip() {
+        for s in [
+            "Monday,Wednesday,Friday",
+            "3 per week",
+            "2 per month",
+            "every 3 days from 2024-05-01",
+            "monthly on 1,15,31",
+        ] {
+            assert_eq!(Schedule::from_str(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Schedule::from_str("2 times a Month"),
+            Ok(Schedule::TimesPerMonth(2))
+        );
+        assert_eq!(
+            Schedule::from_str("every day from 2024-05-01"),
+            Ok(Schedule::EveryNDays {
+                n: 1,
+                start: date("2024-05-01")
+            })
+        );
+        assert_eq!(
+            Schedule::from_str("monthly on 15, 1, 15"),
+            Ok(Schedule::DaysOfMonth(vec![1, 15]))
+        );
+
+        assert_eq!(
+            Schedule::from_str("8 per week"),
+            Err(ParseScheduleError::TimesOutOfRange)
+        );
+        assert_eq!(
+            Schedule::from_str("29 per month"),
+            Err(ParseScheduleError::TimesOutOfRange)
+        );
+        assert_eq!(
+            Schedule::from_str("every 0 days"),
+            Err(ParseScheduleError::EveryZeroDays)
+        );
+        assert_eq!(
+            Schedule::from_str("monthly on 32"),
+            Err(ParseScheduleError::DayOfMonthOutOfRange)
+        );
+        assert_eq!(
+            Schedule::from_str("sometimes"),
+            Err(ParseScheduleError::Unknown)
+        );
+    }
+
+    #[test]
+    fn every_n_days_is_anchored_on_start() {
+        let schedule = Schedule::from_str("every 3 days from 2024-05-01").unwrap();
+        let due: Vec<NaiveDate> = date("2024-04-28")
+            .iter_days()
+            .take(10)
+            .filter(|day| schedule.is_due_on(day))
+            .collect();
+        assert_eq!(
+            due,
+            vec![date("2024-05-01"), date("2024-05-04"), date("2024-05-07")]
+        );
+    }
+
+    #[test]
+    fn days_of_month_after_the_end_of_month_are_its_last_day() {
+        let schedule = Schedule::from_str("monthly on 31").unwrap();
+        assert!(schedule.is_due_on(&date("2024-01-31")));
+        assert!(schedule.is_due_on(&date("2024-02-29")));
+        assert!(!schedule.is_due_on(&date("2024-02-28")));
+        assert!(schedule.is_due_on(&date("2023-02-28")));
+        assert!(schedule.is_due_on(&date("2024-04-30")));
+        assert!(!schedule.is_due_on(&date("2024-05-30")));
+    }
+
+    #[test]
+    fn periods() {
+        // 2024-05-08 is a Wednesday
+        assert_eq!(
+            Schedule::TimesPerWeek(3).period_of(&date("2024-05-08"), &Day::Monday),
+            Some(Period {
+                first: date("2024-05-06"),
+                last: date("2024-05-12"),
+                n: 3
+            })
+        );
+        assert_eq!(
+            Schedule::TimesPerWeek(3).period_of(&date("2024-05-08"), &Day::Sunday),
+            Some(Period {
+                first: date("2024-05-05"),
+                last: date("2024-05-11"),
+                n: 3
+            })
+        );
+        assert_eq!(
+            Schedule::TimesPerMonth(2).period_of(&date("2024-02-10"), &Day::Monday),
+            Some(Period {
+                first: date("2024-02-01"),
+                last: date("2024-02-29"),
+                n: 2
+            })
+        );
+        assert_eq!(
+            Schedule::from_str("daily")
+                .unwrap()
+                .period_of(&date("2024-02-10"), &Day::Monday),
+            None
+        );
+    }
+}
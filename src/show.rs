@@ -1,6 +1,5 @@
 mod cli;
 mod engine;
-mod ui;
 mod viz;
 
 pub use cli::ShowCli;
@@ -9,4 +9,10 @@ pub struct ShowCli {
     ///
     /// Defaults to the habit you most recently logged a rep for.
     pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long = "tag", value_name = "TAG")]
+    /// Only show habits with this tag.
+    /// Can be given several times, for habits with any of the tags.
+    pub tags: Vec<String>,
 }
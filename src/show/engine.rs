@@ -1,16 +1,19 @@
-use super::{viz::BowlOfMarbles, viz::HeatMap, viz::ProgressVisualizer};
+use super::{viz::BowlOfMarbles, viz::HeatMap, viz::HeatMapState, viz::ProgressVisualizer};
+use crate::config::{Config, Visualization};
 use crate::db;
 use crate::engine::Engine;
 use crate::habit::Habit;
 use crate::show::cli::ShowCli;
+use crate::streak::Streaks;
 use crate::tui;
+use anyhow::anyhow;
+use chrono::{Local, NaiveDate};
 use ratatui::buffer::Buffer;
 use ratatui::crossterm::event::KeyEvent;
 use ratatui::layout::Direction;
 use ratatui::layout::Layout;
 use ratatui::layout::Rect;
 use ratatui::prelude::Constraint;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::widgets::Block;
@@ -26,32 +29,55 @@ use ratatui::{
     widgets::Widget,
     Frame,
 };
-use std::io;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
-const PRIMARY_COLOR: Color = Color::LightBlue;
 const POINTED_LIST_ITEM_STYLE: Style = Style::new().add_modifier(Modifier::BOLD);
 
-pub fn get_engine(cli: ShowCli) -> Box<dyn Engine> {
-    Box::new(ShowEngine { habit: cli.habit })
+pub fn get_engine(cli: ShowCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(ShowEngine {
+        db_path,
+        config,
+        habit: cli.habit,
+        tags: cli.tags,
+    })
 }
 
 struct ShowEngine {
+    db_path: PathBuf,
+    config: Config,
     habit: Option<String>,
+    tags: Vec<String>,
 }
 
 impl Engine for ShowEngine {
     fn run(&mut self) -> anyhow::Result<()> {
-        let conn = db::open_db()?;
+        let conn = db::open_db(&self.db_path)?;
 
         // Prepare the data
         // ----------------
+        let today = Local::now().date_naive();
         let init_habit = match self.habit {
             // if provided, go get data from database to construct a Habit
             Some(ref habit_name) => db::habit_get_by_name(&conn, habit_name)?,
+            // if filtering by tag, select the first habit with the tags
+            None if !self.tags.is_empty() => db::habit_get_all(&conn)?
+                .into_iter()
+                .find(|habit| habit.is_active_on(&today) && habit.matches_tags(&self.tags))
+                .ok_or_else(|| anyhow!("No habit with tag {}!", self.tags.join(" or ")))?,
             // if not provided, select the one for which there is the most recent log
             None => db::habit_get_with_most_recent_log(&conn)?,
         };
-        let habits = db::habit_get_all(&conn)?;
+        // archived and paused habits are hidden, as well as those without the tags,
+        // unless asked for
+        let habits: Vec<Habit> = db::habit_get_all(&conn)?
+            .into_iter()
+            .filter(|habit| {
+                (habit.is_active_on(&today) && habit.matches_tags(&self.tags))
+                    || habit.name == init_habit.name
+            })
+            .collect();
         let init_habit_idx = habits
             .iter()
             .position(|habit| habit.name == init_habit.name)
@@ -60,7 +86,8 @@ impl Engine for ShowEngine {
         // Run the TUI
         // -----------
         let mut terminal = tui::init()?;
-        let app_result = App::build(habits, init_habit_idx)?.run(&mut terminal);
+        let app_result =
+            App::build(conn, self.config.clone(), habits, init_habit_idx)?.run(&mut terminal);
         tui::restore(&mut terminal)?;
         app_result?;
 
@@ -70,46 +97,88 @@ impl Engine for ShowEngine {
 
 #[derive(Debug)]
 struct App {
+    conn: Connection,
+    config: Config,
     tabs: Vec<String>,
     visualizers: Vec<ProgressVisualizer>,
     selected_tab_idx: usize,
     habits: Vec<Habit>,
-    habit_names: Vec<String>,
+    habit_rows: Vec<HabitRow>,
     selected_habit_idx: usize,
     habit_list_state: ListState,
+    heatmap_state: HeatMapState,
+    /// Amount logged per day for the selected habit.
+    amount_per_day: HashMap<NaiveDate, usize>,
+    /// Notes and ratings of the reps of each day for the selected habit.
+    annotations_per_day: HashMap<NaiveDate, Vec<String>>,
+    /// Total number of logged reps for the selected habit.
+    n_reps: usize,
+    /// Streaks of the selected habit.
+    streaks: Streaks,
+    /// The habit index `amount_per_day`, `annotations_per_day`, `n_reps` and `streaks`
+    /// were loaded for, if any.
+    totals_key: Option<usize>,
     key_event: Option<KeyEvent>,
     exit: bool,
 }
 
 impl App {
-    fn build(habits: Vec<Habit>, selected_habit_idx: usize) -> anyhow::Result<Self> {
-        let habit_names = habits
-            .iter()
-            .map(|h| h.name.clone())
-            .collect::<Vec<String>>();
+    fn build(
+        conn: Connection,
+        config: Config,
+        habits: Vec<Habit>,
+        selected_habit_idx: usize,
+    ) -> anyhow::Result<Self> {
+        let habit_rows = habit_rows(&habits);
 
         let mut habit_list_state = ListState::default();
-        habit_list_state.select(Some(selected_habit_idx));
+        habit_list_state.select(
+            habit_rows
+                .iter()
+                .position(|row| *row == HabitRow::Habit(selected_habit_idx)),
+        );
+
+        let visualizers = vec![
+            ProgressVisualizer::HeatMap,
+            ProgressVisualizer::BowlOfMarbles,
+        ];
+        let selected_tab_idx = visualizers
+            .iter()
+            .position(|viz| match viz {
+                ProgressVisualizer::HeatMap => {
+                    config.default_visualization == Visualization::Heatmap
+                }
+                ProgressVisualizer::BowlOfMarbles => {
+                    config.default_visualization == Visualization::BowlOfMarbles
+                }
+            })
+            .unwrap_or(0);
 
         Ok(App {
+            conn,
+            config,
             tabs: vec!["Heatmap".to_string(), "Bowl of marbles".to_string()],
-            visualizers: vec![
-                ProgressVisualizer::HeatMap,
-                ProgressVisualizer::BowlOfMarbles,
-            ],
-            selected_tab_idx: 0,
+            visualizers,
+            selected_tab_idx,
             habits,
-            habit_names,
+            habit_rows,
             selected_habit_idx,
             habit_list_state,
+            heatmap_state: HeatMapState::new(Local::now().date_naive()),
+            amount_per_day: HashMap::new(),
+            annotations_per_day: HashMap::new(),
+            n_reps: 0,
+            streaks: Streaks::default(),
+            totals_key: None,
             key_event: None,
             exit: false,
         })
     }
 
     /// runs the application's main loop until the user quits
-    fn run(&mut self, terminal: &mut tui::Tui) -> io::Result<()> {
+    fn run(&mut self, terminal: &mut tui::Tui) -> anyhow::Result<()> {
         while !self.exit {
+            self.load_totals()?;
             terminal.draw(|frame| self.render_frame(frame))?;
             self.handle_events()?;
         }
@@ -120,7 +189,38 @@ impl App {
         frame.render_widget(self, frame.size())
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
+    /// (Re)load the amount logged per day, the notes and ratings per day, the total number
+    /// of logged reps and the streaks if the selected habit changed since the last time
+    /// they were loaded.
+    fn load_totals(&mut self) -> anyhow::Result<()> {
+        if self.totals_key == Some(self.selected_habit_idx) {
+            return Ok(());
+        }
+
+        let habit = &self.habits[self.selected_habit_idx];
+        self.n_reps = db::get_n_logs_for_habit(&self.conn, &habit.name)?;
+        self.amount_per_day = db::get_all_amount_per_day_for_habit(&self.conn, &habit.name)?;
+        self.annotations_per_day = HashMap::new();
+        for log in db::get_logs_for_habit(&self.conn, &habit.name)? {
+            if let Some(annotation) = log.annotation() {
+                self.annotations_per_day
+                    .entry(log.datetime().date_naive())
+                    .or_default()
+                    .push(annotation);
+            }
+        }
+        self.streaks = Streaks::compute(
+            habit,
+            &self.amount_per_day,
+            Local::now().date_naive(),
+            &self.config.week_start,
+        );
+        self.totals_key = Some(self.selected_habit_idx);
+
+        Ok(())
+    }
+
+    fn handle_events(&mut self) -> anyhow::Result<()> {
         // Add a small timeout to the event polling to ensure that the UI
         // remains responsive regardless of whether there are events pending
         // (16ms is ~60fps).
@@ -153,6 +253,27 @@ impl App {
         }
     }
 
+    /// Move the selection of the habit list off tag rows,
+    /// in the direction it moved in if possible.
+    fn skip_tag_rows(&mut self, forward: bool) {
+        let Some(selected) = self.habit_list_state.selected() else {
+            return;
+        };
+        // the selection can go past the last row, only being clamped when rendered
+        let mut i = selected.min(self.habit_rows.len() - 1);
+        let mut forward = forward;
+        while let HabitRow::Tag(_) = self.habit_rows[i] {
+            if forward && i + 1 < self.habit_rows.len() {
+                i += 1;
+            } else if !forward && i > 0 {
+                i -= 1;
+            } else {
+                forward = !forward;
+            }
+        }
+        self.habit_list_state.select(Some(i));
+    }
+
     fn next_viz(&mut self) {
         self.selected_tab_idx = (self.selected_tab_idx + 1) % self.tabs.len();
     }
@@ -166,6 +287,45 @@ impl App {
     }
 }
 
+/// A row of the habit list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HabitRow {
+    /// The title of a group of habits with the same tag.
+    Tag(String),
+    /// A habit, by index.
+    Habit(usize),
+}
+
+/// The rows of the habit list: habits grouped by tag if any have tags,
+/// the habits without tags coming last. Habits with several tags are in each group.
+fn habit_rows(habits: &[Habit]) -> Vec<HabitRow> {
+    let mut tags: Vec<&String> = habits.iter().flat_map(|habit| habit.tags.iter()).collect();
+    if tags.is_empty() {
+        return (0..habits.len()).map(HabitRow::Habit).collect();
+    }
+    tags.sort();
+    tags.dedup();
+
+    let mut rows = Vec::new();
+    for tag in tags {
+        rows.push(HabitRow::Tag(tag.clone()));
+        rows.extend(
+            (0..habits.len())
+                .filter(|i| habits[*i].tags.contains(tag))
+                .map(HabitRow::Habit),
+        );
+    }
+    let untagged: Vec<usize> = (0..habits.len())
+        .filter(|i| habits[*i].tags.is_empty())
+        .collect();
+    if !untagged.is_empty() {
+        rows.push(HabitRow::Tag(String::from("untagged")));
+        rows.extend(untagged.into_iter().map(HabitRow::Habit));
+    }
+
+    rows
+}
+
 impl Widget for &mut App {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Layout
@@ -188,26 +348,51 @@ impl Widget for &mut App {
                 match key_event.code {
                     KeyCode::Char('j') | KeyCode::Down => {
                         self.habit_list_state.select_next();
+                        self.skip_tag_rows(true);
                     }
                     KeyCode::Char('k') | KeyCode::Up => {
                         self.habit_list_state.select_previous();
+                        self.skip_tag_rows(false);
                     }
                     KeyCode::Char('g') | KeyCode::Home => {
                         self.habit_list_state.select_first();
+                        self.skip_tag_rows(true);
                     }
                     KeyCode::Char('G') | KeyCode::End => {
                         self.habit_list_state.select_last();
+                        self.skip_tag_rows(false);
                     }
                     KeyCode::Enter => {
-                        self.selected_habit_idx = self
+                        let selected = self
                             .habit_list_state
                             .selected()
                             .expect("There should always be a habit selected.");
+                        if let HabitRow::Habit(i) = self.habit_rows[selected] {
+                            self.selected_habit_idx = i;
+                        }
                     }
                     KeyCode::Tab => self.next_viz(),
                     KeyCode::BackTab => self.prev_viz(),
                     _ => {}
                 }
+
+                // Heatmap specific keys
+                if let ProgressVisualizer::HeatMap = self.visualizers[self.selected_tab_idx] {
+                    match key_event.code {
+                        KeyCode::Char('h') | KeyCode::Left => {
+                            self.heatmap_state.select_previous_week();
+                        }
+                        KeyCode::Char('l') | KeyCode::Right => {
+                            self.heatmap_state.select_next_week();
+                        }
+                        KeyCode::Char('K') => self.heatmap_state.select_previous_day(),
+                        KeyCode::Char('J') => self.heatmap_state.select_next_day(),
+                        KeyCode::Char('[') => self.heatmap_state.select_previous_year(),
+                        KeyCode::Char(']') => self.heatmap_state.select_next_year(),
+                        KeyCode::Char('t') => self.heatmap_state.select_today(),
+                        _ => {}
+                    }
+                }
             }
         }
 
@@ -218,21 +403,20 @@ impl Widget for &mut App {
         let tabs = Tabs::new(self.tabs.clone())
             .block(tabs_block)
             .style(Style::default().white())
-            .highlight_style(PRIMARY_COLOR)
+            .highlight_style(self.config.primary_color())
             .select(self.selected_tab_idx);
 
         // Habit list
         let habit_list_block = Block::bordered().title("Habits");
         let items: Vec<ListItem> = self
-            .habit_names
+            .habit_rows
             .iter()
-            .enumerate()
-            .map(|(i, habit)| {
-                if i == self.selected_habit_idx {
-                    ListItem::from(habit.clone()).style(PRIMARY_COLOR)
-                } else {
-                    ListItem::from(habit.clone())
+            .map(|row| match row {
+                HabitRow::Tag(tag) => ListItem::from(tag.clone()).dark_gray().italic(),
+                HabitRow::Habit(i) if *i == self.selected_habit_idx => {
+                    ListItem::from(self.habits[*i].name.clone()).style(self.config.primary_color())
                 }
+                HabitRow::Habit(i) => ListItem::from(self.habits[*i].name.clone()),
             })
             .collect();
         let habit_list = List::new(items)
@@ -250,19 +434,21 @@ impl Widget for &mut App {
         let selected_habit = &self.habits[self.selected_habit_idx];
         // selected_tab_idx should always be within the bounds of visualizers
         match self.visualizers[self.selected_tab_idx] {
-            ProgressVisualizer::HeatMap => HeatMap::new(selected_habit).render(viz_area, buf),
+            ProgressVisualizer::HeatMap => StatefulWidget::render(
+                HeatMap::new(
+                    selected_habit,
+                    &self.amount_per_day,
+                    &self.annotations_per_day,
+                    &self.streaks,
+                    &self.config,
+                ),
+                viz_area,
+                buf,
+                &mut self.heatmap_state,
+            ),
             ProgressVisualizer::BowlOfMarbles => {
-                BowlOfMarbles::new(selected_habit).render(viz_area, buf)
+                BowlOfMarbles::new(selected_habit, self.n_reps).render(viz_area, buf)
             }
         }
-
-        // // Show current number of logged reps
-        // let n_reps = db::get_n_logs_for_habit(&conn, habit)?;
-        // println!(
-        //     "You accumulated {} for habit '{}'. {}",
-        //     format!("{} {}", n_reps, if n_reps <= 1 { "rep" } else { "reps" }).bold(),
-        //     habit,
-        //     if n_reps > 0 { "Congratulations!" } else { "" }
-        // );
     }
 }
@@ -1,21 +0,0 @@
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-
-pub fn centered_rect(r: Rect, percent_width: u16, percent_height: u16) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_height) / 2),
-            Constraint::Percentage(percent_height),
-            Constraint::Percentage((100 - percent_height) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_width) / 2),
-            Constraint::Percentage(percent_width),
-            Constraint::Percentage((100 - percent_width) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
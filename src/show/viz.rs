@@ -2,7 +2,7 @@ mod bowl_of_marbles;
 mod heatmap;
 
 pub use bowl_of_marbles::BowlOfMarbles;
-pub use heatmap::HeatMap;
+pub use heatmap::{HeatMap, HeatMapState};
 
 #[derive(Debug, Clone, Copy)]
 pub enum ProgressVisualizer {
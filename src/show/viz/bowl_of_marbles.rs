@@ -1,23 +1,168 @@
 use crate::habit::Habit;
-use crate::show::ui::centered_rect;
+use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::prelude::{Buffer, Rect};
-use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
+
+/// Number of reps needed to earn each badge.
+const MILESTONES: [usize; 3] = [100, 500, 1000];
+
+/// The marbles, from the smallest to the biggest,
+/// along with their color. The marble at index i is worth 10^i reps.
+const MARBLES: [(&str, Color); 3] = [
+    ("o", Color::LightBlue),
+    ("O", Color::LightMagenta),
+    ("@", Color::LightYellow),
+];
+
+/// Maximum number of rows of marbles the bowl can have.
+const MAX_BOWL_HEIGHT: u16 = 10;
 
 pub struct BowlOfMarbles<'a> {
     habit: &'a Habit,
+    n_reps: usize,
 }
 
 impl<'a> BowlOfMarbles<'a> {
-    pub fn new(habit: &'a Habit) -> Self {
-        BowlOfMarbles { habit }
+    pub fn new(habit: &'a Habit, n_reps: usize) -> Self {
+        BowlOfMarbles { habit, n_reps }
     }
 }
 
+/// Decompose `n_reps` into marbles so that they fit in a bowl of `capacity` marbles,
+/// using the smallest marbles possible.
+///
+/// Returns the number of marbles of each size (see MARBLES), and whether
+/// they all fit in the bowl (they may not, even using only the biggest marbles).
+fn decompose(n_reps: usize, capacity: usize) -> ([usize; MARBLES.len()], bool) {
+    let mut n_marbles = [0; MARBLES.len()];
+    for biggest in 0..MARBLES.len() {
+        // Use as many of the biggest marbles as possible,
+        // then make up for the remainder with smaller ones.
+        let mut rest = n_reps;
+        for size in (0..=biggest).rev() {
+            let worth = 10_usize.pow(size as u32);
+            n_marbles[size] = rest / worth;
+            rest %= worth;
+        }
+
+        if n_marbles.iter().sum::<usize>() <= capacity {
+            return (n_marbles, true);
+        }
+    }
+
+    (n_marbles, false)
+}
+
 impl<'a> Widget for BowlOfMarbles<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let rect = centered_rect(area, 50, 50);
-        let para = Paragraph::new(format!("Bowl of marbles for habit {}", self.habit.name))
-            .block(Block::bordered());
-        para.render(rect, buf);
+        // The bowl is a trapezoid whose inner width shrinks by 2 at each row,
+        // from 4 times its height at the top, so that it looks round-ish
+        // given terminal cells are about twice as high as wide.
+        // 6 rows are needed for the texts above and below the bowl, and its bottom.
+        let h = MAX_BOWL_HEIGHT
+            .min(area.height.saturating_sub(6))
+            .min(area.width.saturating_sub(2) / 4);
+        let top_inner_w = 4 * h;
+        let inner_w = |row: u16| top_inner_w - 2 * row;
+        let capacity = (0..h).map(|row| inner_w(row) as usize).sum::<usize>();
+
+        let [_, total_area, _, bowl_area, _, legend_area, badges_area, _] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(h + 1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Fill(1),
+            ])
+            .areas(area);
+        let [_, bowl_area, _] = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(top_inner_w + 2),
+                Constraint::Fill(1),
+            ])
+            .areas(bowl_area);
+
+        // Total
+        Line::from(vec![
+            Span::from(format!("{}: ", self.habit.name)),
+            Span::from(format!(
+                "{} {}",
+                self.n_reps,
+                if self.n_reps <= 1 { "rep" } else { "reps" }
+            ))
+            .bold(),
+        ])
+        .centered()
+        .render(total_area, buf);
+
+        // Bowl
+        let (n_marbles, all_fit) = decompose(self.n_reps, capacity);
+        // Marbles to put in the bowl, biggest first so that they end up at the bottom.
+        let mut marbles = n_marbles
+            .iter()
+            .enumerate()
+            .rev()
+            .flat_map(|(size, n)| std::iter::repeat_n(MARBLES[size], *n));
+        for row in (0..h).rev() {
+            let y = bowl_area.y + row;
+            let x = bowl_area.x + row;
+            buf.set_string(x, y, "\\", Style::new());
+            for dx in 1..=inner_w(row) {
+                if let Some((glyph, color)) = marbles.next() {
+                    buf.set_string(x + dx, y, glyph, Style::new().fg(color).bold());
+                }
+            }
+            buf.set_string(x + inner_w(row) + 1, y, "/", Style::new());
+        }
+        buf.set_string(
+            bowl_area.x + h,
+            bowl_area.y + h,
+            format!("\\{}/", "_".repeat(inner_w(h) as usize)),
+            Style::new(),
+        );
+
+        // Legend
+        let biggest = n_marbles.iter().rposition(|n| *n > 0).unwrap_or(0);
+        let mut legend: Vec<Span> = (0..=biggest)
+            .flat_map(|size| {
+                let (glyph, color) = MARBLES[size];
+                let worth = 10_usize.pow(size as u32);
+                [
+                    Span::styled(glyph, Style::new().fg(color).bold()),
+                    Span::from(format!(
+                        " = {} {}   ",
+                        worth,
+                        if worth <= 1 { "rep" } else { "reps" }
+                    )),
+                ]
+            })
+            .collect();
+        if !all_fit {
+            legend.push(Span::from("(the bowl is full!)").italic());
+        }
+        Line::from(legend).centered().render(legend_area, buf);
+
+        // Badges
+        let badges: Vec<Span> = MILESTONES
+            .iter()
+            .flat_map(|milestone| {
+                let badge = Span::from(format!(" {} reps ", milestone));
+                let badge = if self.n_reps >= *milestone {
+                    badge.black().on_light_yellow().bold()
+                } else {
+                    badge.dark_gray().on_black()
+                };
+                [badge, Span::from(" ")]
+            })
+            .collect();
+        Line::from(badges).centered().render(badges_area, buf);
     }
 }
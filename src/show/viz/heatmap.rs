@@ -1,20 +1,101 @@
-use crate::habit::Habit;
-use crate::utils;
-use chrono::{Datelike, Weekday};
-use chrono::{TimeZone, Utc};
+use crate::config::Config;
+use crate::habit::{Day, Habit};
+use crate::streak::Streaks;
+use chrono::{Datelike, Days, Local, Months, NaiveDate};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::prelude::{Buffer, Rect};
 use ratatui::style::{Style, Stylize};
-use ratatui::text::Span;
-use ratatui::widgets::Widget;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{StatefulWidget, Widget};
+use std::collections::HashMap;
 
 pub struct HeatMap<'a> {
     habit: &'a Habit,
+    amount_per_day: &'a HashMap<NaiveDate, usize>,
+    annotations_per_day: &'a HashMap<NaiveDate, Vec<String>>,
+    streaks: &'a Streaks,
+    config: &'a Config,
 }
 
 impl<'a> HeatMap<'a> {
-    pub fn new(habit: &'a Habit) -> Self {
-        HeatMap { habit }
+    /// `amount_per_day` must hold the amount logged per day,
+    /// at least for the year of the date selected in the `HeatMapState`
+    /// and the weeks or months around it.
+    /// `annotations_per_day` holds the notes and ratings of the reps of each day
+    /// (see `Log::annotation`), shown for the selected day.
+    pub fn new(
+        habit: &'a Habit,
+        amount_per_day: &'a HashMap<NaiveDate, usize>,
+        annotations_per_day: &'a HashMap<NaiveDate, Vec<String>>,
+        streaks: &'a Streaks,
+        config: &'a Config,
+    ) -> Self {
+        HeatMap {
+            habit,
+            amount_per_day,
+            annotations_per_day,
+            streaks,
+            config,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// The state of a `HeatMap`, i.e. the date under the cursor.
+/// The year shown is always the one of the selected date.
+pub struct HeatMapState {
+    selected: NaiveDate,
+}
+
+impl HeatMapState {
+    pub fn new(selected: NaiveDate) -> Self {
+        HeatMapState { selected }
+    }
+
+    pub fn year(&self) -> i32 {
+        self.selected.year()
+    }
+
+    pub fn selected(&self) -> NaiveDate {
+        self.selected
+    }
+
+    pub fn select_today(&mut self) {
+        self.selected = Local::now().date_naive();
+    }
+
+    pub fn select_next_day(&mut self) {
+        self.select(self.selected.checked_add_days(Days::new(1)));
+    }
+
+    pub fn select_previous_day(&mut self) {
+        self.select(self.selected.checked_sub_days(Days::new(1)));
+    }
+
+    pub fn select_next_week(&mut self) {
+        self.select(self.selected.checked_add_days(Days::new(7)));
+    }
+
+    pub fn select_previous_week(&mut self) {
+        self.select(self.selected.checked_sub_days(Days::new(7)));
+    }
+
+    pub fn select_next_year(&mut self) {
+        self.select(self.selected.checked_add_months(Months::new(12)));
+    }
+
+    pub fn select_previous_year(&mut self) {
+        self.select(self.selected.checked_sub_months(Months::new(12)));
+    }
+
+    /// Select `date` if any, unless it is in a year after the current one
+    /// (there is nothing to see there).
+    fn select(&mut self, date: Option<NaiveDate>) {
+        if let Some(date) = date {
+            if date.year() <= Local::now().year() {
+                self.selected = date;
+            }
+        }
     }
 }
 
@@ -29,10 +110,15 @@ enum DayType {
     /// A day for which the habit need not be performed/logged.
     ShouldNotHabit,
     /// A day for which the habit need to be performed/logged.
-    /// Contains a boolean indicating whether it was effectively logged or not.
-    ShouldHabit(bool),
+    /// Contains the amount logged that day.
+    ShouldHabit(usize),
 }
 
+/// The glyphs of days the habit was scheduled on, by increasing amount logged:
+/// nothing, less than half the daily target, less than the daily target,
+/// the daily target, twice the daily target or more.
+const INTENSITY_GLYPHS: [&str; 5] = ["0", "░", "▒", "▓", "█"];
+
 /// Add a red background to the span if it corresponds to today,
 /// otherwise return as is.
 macro_rules! highlight_if_today {
@@ -47,78 +133,115 @@ macro_rules! highlight_if_today {
     }};
 }
 
-impl<'a> Widget for HeatMap<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        // 7 days for 53 weeks, the maximum there can be in a year
-        let w = 53;
+impl<'a> HeatMap<'a> {
+    /// The cell of `day` the habit was scheduled on, where `amount` was logged.
+    fn intensity_span(&self, day: &NaiveDate, amount: usize) -> Span<'static> {
+        let target = self.habit.target_on(day);
+        let level = if amount == 0 {
+            0
+        } else if 2 * amount < target {
+            1
+        } else if amount < target {
+            2
+        } else if amount < 2 * target {
+            3
+        } else {
+            4
+        };
+        let color = if self.habit.is_done_with(amount, day) {
+            self.config.done_color()
+        } else {
+            self.config.missed_color()
+        };
+
+        Span::styled(INTENSITY_GLYPHS[level], Style::new().fg(color).bold())
+    }
+}
+
+impl<'a> StatefulWidget for HeatMap<'a> {
+    type State = HeatMapState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut HeatMapState) {
+        let year = state.year();
+        let first_day_of_year = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+        let last_day_of_year = NaiveDate::from_ymd_opt(year, 12, 31).unwrap();
+        let n_days_in_year = last_day_of_year.ordinal() as usize;
+        let today = Local::now().date_naive();
+
+        // Weeks start on the configured day, so the first cell of the matrix is
+        // the first day of the week containing the 1st of January.
+        let start_idx = first_day_of_year
+            .weekday()
+            .days_since(self.config.week_start.clone().into()) as usize;
+        // 7 days for as many weeks as needed to cover the year (53 or 54)
         let h = 7;
+        let w = (start_idx + n_days_in_year).div_ceil(h);
 
         // Make a centered rect for the heatmap,
         // leveraging our knowledge of the exact number
         // of rows and columns it will have.
-        let [_, rect, _] = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Fill(1),
-                Constraint::Length(h),
-                Constraint::Fill(1),
-            ])
-            .areas(area);
+        // The year is shown above it and the details of the selected day below.
+        let [_, year_area, _, rect, _, status_area, notes_area, streaks_area, _] =
+            Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Fill(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(h as u16),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Length(1),
+                    Constraint::Fill(1),
+                ])
+                .areas(area);
         let [_, rect, _] = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
                 Constraint::Fill(1),
-                Constraint::Length(2 * w),
+                Constraint::Length(2 * w as u16),
                 Constraint::Fill(1),
             ])
             .areas(rect);
 
-        // Make a days matrix, a 7 by 53 matrix where each cell corresponds to a day of the year.
+        // Make a days matrix, a 7 by w matrix where each cell corresponds to a day of the year.
         // A cell contains the "type" of the day it corresponds to (see DayType).
-        let mut days_mat: Vec<DayType> = vec![DayType::ShouldNotHabit; (w as usize) * (h as usize)];
-
-        let year = 2024;
-        let first_day_of_year = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).unwrap();
-        let first_weekday_of_year = first_day_of_year.weekday();
-        let last_day_of_year = Utc.with_ymd_and_hms(year, 12, 31, 0, 0, 0).unwrap();
-        let last_weekday_of_year = last_day_of_year.weekday();
-        let today = Utc::now();
-
-        // Set all days in previous year to DayType::NotInYear.
-        let mut start_idx: usize = 0;
-        while Weekday::try_from((start_idx % 7) as u8).unwrap() != first_weekday_of_year {
-            days_mat[start_idx] = DayType::NotInYear;
-            start_idx += 1;
-        }
-        // Set all days in next year to DayType::NotInYear.
-        let mut end_idx: usize = days_mat.capacity() - 1;
-        while Weekday::try_from((end_idx % 7) as u8).unwrap() != last_weekday_of_year {
-            days_mat[end_idx] = DayType::NotInYear;
-            end_idx -= 1;
-        }
-
-        // For all days after today, set to DayType::ToCome.
+        // Days in the previous or next year are DayType::NotInYear.
+        let mut days_mat: Vec<DayType> = vec![DayType::NotInYear; w * h];
         let mut today_idx_opt: Option<usize> = None;
-        if today.year() == year {
-            let today_year_offset = utils::nth_day_of_year(&today);
-            today_idx_opt = Some((today_year_offset as usize) - 1);
-            for d in days_mat[start_idx + (today_year_offset as usize)..end_idx + 1].iter_mut() {
-                *d = DayType::ToCome;
+        let expected_days = self.habit.expected_days(
+            self.amount_per_day,
+            first_day_of_year,
+            last_day_of_year,
+            today,
+            &self.config.week_start,
+        );
+        let selected_idx = start_idx + state.selected().ordinal0() as usize;
+        for (offset, day) in first_day_of_year
+            .iter_days()
+            .take(n_days_in_year)
+            .enumerate()
+        {
+            let i = start_idx + offset;
+            if day == today {
+                today_idx_opt = Some(i);
             }
-        }
 
-        // TODO: Select logs for habit in year.
-        // Use the day number (in year) as an offset into the matrix.
-        let done = vec![(1, true), (8, true), (15, false), (22, true)];
-        for (i, b) in done {
-            days_mat[i] = DayType::ShouldHabit(b);
+            days_mat[i] = if day > today {
+                DayType::ToCome
+            } else if expected_days.contains(&day) {
+                DayType::ShouldHabit(self.amount_per_day.get(&day).copied().unwrap_or(0))
+            } else {
+                DayType::ShouldNotHabit
+            };
         }
 
         let mut i = 0;
         let start_x = rect.x;
-        let end_x = start_x + 2 * w; // 2*w because one char for the cell and one space for the gutter
+        let end_x = start_x + 2 * w as u16; // 2*w because one char for the cell and one space for the gutter
         let start_y = rect.y;
-        let end_y = start_y + h;
+        let end_y = start_y + h as u16;
         for x in (start_x..end_x).step_by(2) {
             for y in start_y..end_y {
                 let span = match days_mat[i] {
@@ -127,16 +250,15 @@ impl<'a> Widget for HeatMap<'a> {
                     DayType::ShouldNotHabit => {
                         highlight_if_today!(today_idx_opt, i, Span::from("_"))
                     }
-                    DayType::ShouldHabit(true) => highlight_if_today!(
-                        today_idx_opt,
-                        i,
-                        Span::styled("1", Style::new().green().bold())
-                    ),
-                    DayType::ShouldHabit(false) => highlight_if_today!(
-                        today_idx_opt,
-                        i,
-                        Span::styled("0", Style::new().red().bold())
-                    ),
+                    DayType::ShouldHabit(amount) => {
+                        let day = first_day_of_year + Days::new((i - start_idx) as u64);
+                        highlight_if_today!(today_idx_opt, i, self.intensity_span(&day, amount))
+                    }
+                };
+                let span = if i == selected_idx {
+                    span.patch_style(Style::new().reversed())
+                } else {
+                    span
                 };
 
                 buf.set_span(x, y, &span, 1);
@@ -145,8 +267,53 @@ impl<'a> Widget for HeatMap<'a> {
             }
         }
 
-        // TODO: Make it stateful:
-        //  1. Color and show date on hover on cell
-        //  2. add events to paginate forward or backward
+        Line::from(vec![
+            Span::from("< ").dark_gray(),
+            Span::from(year.to_string()).bold(),
+            Span::from(" >").dark_gray(),
+        ])
+        .centered()
+        .render(year_area, buf);
+
+        let selected = state.selected();
+        let amount = self.amount_per_day.get(&selected).copied().unwrap_or(0);
+        Line::from(format!(
+            "{}, {}: {}",
+            Day::from(selected.weekday()),
+            selected.format(&self.config.date_format),
+            if self.habit.is_measured() || self.habit.at.on(&selected).len() > 1 {
+                format!(
+                    "{}/{}",
+                    amount,
+                    self.habit.display_amount(self.habit.target_on(&selected))
+                )
+            } else {
+                self.habit.display_amount(amount)
+            }
+        ))
+        .centered()
+        .render(status_area, buf);
+
+        if let Some(annotations) = self.annotations_per_day.get(&selected) {
+            Line::from(annotations.join(" | "))
+                .centered()
+                .italic()
+                .render(notes_area, buf);
+        }
+
+        Line::from(format!(
+            "Current streak: {}   Longest streak: {}",
+            self.streaks
+                .current
+                .map_or(String::from("0"), |streak| streak
+                    .format(&self.config.date_format)),
+            self.streaks
+                .longest
+                .map_or(String::from("0"), |streak| streak
+                    .format(&self.config.date_format))
+        ))
+        .centered()
+        .dark_gray()
+        .render(streaks_area, buf);
     }
 }
@@ -0,0 +1,30 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Show statistics about your habits.
+///
+/// For each habit: the completion rate (scheduled days with a rep
+/// over scheduled days) for the last 7, 30 and 90 days and for the
+/// current year, the average number of reps per week, the best and
+/// worst weekdays, and how the time of the reps compares to the time
+/// set for the habit.
+/// Only days since the first logged rep of the habit are considered.
+pub struct StatsCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to show statistics for.
+    ///
+    /// Defaults to all habits.
+    pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, action)]
+    /// Output statistics as JSON.
+    pub json: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long = "tag", value_name = "TAG", conflicts_with = "habit")]
+    /// Only show statistics for habits with this tag.
+    /// Can be given several times, for habits with any of the tags.
+    pub tags: Vec<String>,
+}
@@ -0,0 +1,286 @@
+use crate::config::Config;
+use crate::db;
+use crate::engine::Engine;
+use crate::habit::{Day, Habit, Log};
+use crate::stats::cli::StatsCli;
+use crate::utils;
+use chrono::{Datelike, Days, Local, NaiveDate, Timelike};
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Maximum number of minutes between a rep and the closest time set for the habit
+/// for the rep to be considered on time.
+const ON_TIME_TOLERANCE: i64 = 30;
+
+pub fn get_engine(cli: StatsCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(StatsEngine {
+        db_path,
+        config,
+        habit: cli.habit,
+        json: cli.json,
+        tags: cli.tags,
+    })
+}
+
+struct StatsEngine {
+    db_path: PathBuf,
+    config: Config,
+    habit: Option<String>,
+    json: bool,
+    tags: Vec<String>,
+}
+
+/// Statistics about a habit.
+/// Rates are between 0 and 1, and are None when there is nothing to compute them on.
+#[derive(Debug, Clone, Serialize)]
+struct HabitStats {
+    habit: String,
+    completion_last_7_days: Option<f64>,
+    completion_last_30_days: Option<f64>,
+    completion_last_90_days: Option<f64>,
+    completion_this_year: Option<f64>,
+    reps_per_week: f64,
+    best_day: Option<Day>,
+    worst_day: Option<Day>,
+    timing: Option<Timing>,
+}
+
+/// How the time of the reps compares to the closest time set for the habit.
+#[derive(Debug, Clone, Serialize)]
+struct Timing {
+    /// Average number of minutes between the reps and the closest time set for the habit,
+    /// negative if early.
+    mean_offset_minutes: f64,
+    early: f64,
+    on_time: f64,
+    late: f64,
+}
+
+impl Engine for StatsEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        let habits = match self.habit {
+            Some(ref habit_name) => vec![db::habit_get_by_name(&conn, habit_name)?],
+            None => db::habit_get_all(&conn)?
+                .into_iter()
+                .filter(|habit| !habit.archived && habit.matches_tags(&self.tags))
+                .collect(),
+        };
+        let today = Local::now().date_naive();
+        let mut stats = Vec::new();
+        for habit in habits.iter() {
+            let logs = db::get_logs_for_habit(&conn, &habit.name)?;
+            stats.push(compute_stats(habit, &logs, today, &self.config.week_start));
+        }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+        } else {
+            print_table(&stats);
+        }
+
+        Ok(())
+    }
+}
+
+fn compute_stats(habit: &Habit, logs: &[Log], today: NaiveDate, week_start: &Day) -> HabitStats {
+    let mut amount_per_day: HashMap<NaiveDate, usize> = HashMap::new();
+    for log in logs {
+        *amount_per_day
+            .entry(log.datetime().date_naive())
+            .or_default() += log.value as usize;
+    }
+    let done_days: HashSet<NaiveDate> = amount_per_day
+        .iter()
+        .filter(|(day, amount)| habit.is_done_with(**amount, day))
+        .map(|(day, _)| *day)
+        .collect();
+    let first_day = amount_per_day.keys().min().copied();
+    let expected_days = match first_day {
+        Some(first_day) => {
+            habit.expected_days(&amount_per_day, first_day, today, today, week_start)
+        }
+        None => HashSet::new(),
+    };
+
+    // Days considered for completion rates: expected days since the first rep,
+    // today being considered only once done.
+    let considered_days = |since: NaiveDate| -> Vec<NaiveDate> {
+        let Some(first_day) = first_day else {
+            return Vec::new();
+        };
+        since
+            .max(first_day)
+            .iter_days()
+            .take_while(|day| *day <= today)
+            .filter(|day| expected_days.contains(day))
+            .filter(|day| *day < today || done_days.contains(day))
+            .collect()
+    };
+    let completion_rate = |days: &[NaiveDate]| -> Option<f64> {
+        if days.is_empty() {
+            return None;
+        }
+        let n_done = days.iter().filter(|day| done_days.contains(day)).count();
+        Some(n_done as f64 / days.len() as f64)
+    };
+    let completion_since =
+        |n_days: u64| completion_rate(&considered_days(today - Days::new(n_days - 1)));
+
+    // Completion rates per weekday, over all considered days.
+    let mut days_per_weekday: HashMap<Day, Vec<NaiveDate>> = HashMap::new();
+    for day in considered_days(NaiveDate::MIN) {
+        days_per_weekday
+            .entry(Day::from(day.weekday()))
+            .or_default()
+            .push(day);
+    }
+    let mut weekday_rates: Vec<(Day, f64)> = days_per_weekday
+        .into_iter()
+        .filter_map(|(day, days)| completion_rate(&days).map(|rate| (day, rate)))
+        .collect();
+    // Sort by rate, then by day so that ties are broken consistently.
+    weekday_rates.sort_by(|(d1, r1), (d2, r2)| r1.total_cmp(r2).then(d2.cmp(d1)));
+
+    let reps_per_week = match first_day {
+        Some(first_day) => {
+            let n_weeks = ((today - first_day).num_days() + 1) as f64 / 7.0;
+            logs.len() as f64 / n_weeks.max(1.0)
+        }
+        None => 0.0,
+    };
+
+    HabitStats {
+        habit: habit.name.clone(),
+        completion_last_7_days: completion_since(7),
+        completion_last_30_days: completion_since(30),
+        completion_last_90_days: completion_since(90),
+        completion_this_year: completion_rate(&considered_days(
+            NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap(),
+        )),
+        reps_per_week,
+        best_day: weekday_rates.last().map(|(day, _)| day.clone()),
+        worst_day: weekday_rates.first().map(|(day, _)| day.clone()),
+        timing: compute_timing(habit, logs),
+    }
+}
+
+fn compute_timing(habit: &Habit, logs: &[Log]) -> Option<Timing> {
+    if logs.is_empty() {
+        return None;
+    }
+
+    let offsets: Vec<i64> = logs
+        .iter()
+        .map(|log| {
+            let datetime = log.datetime();
+            let log_minutes = datetime.hour() as i64 * 60 + datetime.minute() as i64;
+            // Compare to the closest of the times of the day.
+            habit
+                .at
+                .on(&datetime.date_naive())
+                .iter()
+                .map(|at| {
+                    let at_minutes = at.hour as i64 * 60 + at.minutes as i64;
+                    // Bring the offset in [-12h, 12h[, so that a rep done just after
+                    // midnight for a habit set at 23:30 counts as a bit late.
+                    (log_minutes - at_minutes + 12 * 60).rem_euclid(24 * 60) - 12 * 60
+                })
+                .min_by_key(|offset| offset.abs())
+                .expect("Habits should have a time on every day.")
+        })
+        .collect();
+
+    let n = offsets.len() as f64;
+    let rate_of = |pred: fn(i64) -> bool| offsets.iter().filter(|o| pred(**o)).count() as f64 / n;
+    Some(Timing {
+        mean_offset_minutes: offsets.iter().sum::<i64>() as f64 / n,
+        early: rate_of(|o| o < -ON_TIME_TOLERANCE),
+        on_time: rate_of(|o| o.abs() <= ON_TIME_TOLERANCE),
+        late: rate_of(|o| o > ON_TIME_TOLERANCE),
+    })
+}
+
+fn print_table(stats: &[HabitStats]) {
+    let name_width = stats
+        .iter()
+        .map(|s| s.habit.len())
+        .chain(["HABIT".len()])
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{}",
+        format!(
+            "{:<name_width$}  {:>5}  {:>5}  {:>5}  {:>5}  {:>8}  {:<9}  {:<9}  TIMING",
+            "HABIT", "7D", "30D", "90D", "YEAR", "REPS/WK", "BEST DAY", "WORST DAY"
+        )
+        .bold()
+    );
+    for s in stats {
+        println!(
+            "{:<name_width$}  {}  {}  {}  {}  {:>8.1}  {:<9}  {:<9}  {}",
+            s.habit,
+            colored_rate(s.completion_last_7_days),
+            colored_rate(s.completion_last_30_days),
+            colored_rate(s.completion_last_90_days),
+            colored_rate(s.completion_this_year),
+            s.reps_per_week,
+            s.best_day
+                .as_ref()
+                .map_or(String::from("-"), |day| day.to_string()),
+            s.worst_day
+                .as_ref()
+                .map_or(String::from("-"), |day| day.to_string()),
+            s.timing.as_ref().map_or(String::from("-"), |timing| {
+                format!(
+                    "{} on time, {} early, {} late, {} on average",
+                    percentage(timing.on_time),
+                    percentage(timing.early),
+                    percentage(timing.late),
+                    display_offset(timing.mean_offset_minutes.round() as i64)
+                )
+            }),
+        );
+    }
+}
+
+/// A rate as a 5 characters wide percentage, green if good, red if bad.
+fn colored_rate(rate: Option<f64>) -> ColoredString {
+    match rate {
+        Some(rate) => {
+            let s = format!("{:>5}", percentage(rate));
+            if rate >= 0.8 {
+                s.green()
+            } else if rate >= 0.5 {
+                s.yellow()
+            } else {
+                s.red()
+            }
+        }
+        None => format!("{:>5}", "-").bright_black(),
+    }
+}
+
+fn percentage(rate: f64) -> String {
+    format!("{}%", (rate * 100.0).round())
+}
+
+/// A signed number of minutes as e.g. "+1h05" or "-12m".
+fn display_offset(minutes: i64) -> String {
+    let sign = if minutes < 0 { "-" } else { "+" };
+    let minutes = minutes.abs();
+    if minutes < 60 {
+        format!("{}{}m", sign, minutes)
+    } else {
+        format!(
+            "{}{}h{}",
+            sign,
+            minutes / 60,
+            utils::left_pad(&(minutes % 60).to_string(), '0', 2)
+        )
+    }
+}
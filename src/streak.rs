@@ -0,0 +1,175 @@
+use crate::habit::{Day, Habit};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+
+/// Consecutive days the habit was expected and done on, i.e. its daily target was reached
+/// (see Habit::expected_days).
+/// Days the habit is not expected on neither count nor break a streak.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    /// Number of scheduled days in the streak.
+    pub length: usize,
+    /// First scheduled day of the streak.
+    pub start: NaiveDate,
+    /// Last scheduled day of the streak.
+    pub end: NaiveDate,
+}
+
+impl Streak {
+    /// Describe the streak, e.g. "3 (2024-05-01 to 2024-05-03)",
+    /// with dates formatted according to `date_format`.
+    pub fn format(&self, date_format: &str) -> String {
+        if self.start == self.end {
+            format!("{} ({})", self.length, self.start.format(date_format))
+        } else {
+            format!(
+                "{} ({} to {})",
+                self.length,
+                self.start.format(date_format),
+                self.end.format(date_format)
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Streaks {
+    /// The streak still going on, if any.
+    /// Not having performed the habit yet today does not break it.
+    pub current: Option<Streak>,
+    /// The longest streak ever, the most recent one if there are several.
+    pub longest: Option<Streak>,
+}
+
+impl Streaks {
+    /// Compute the streaks of `habit` up to `today`, given the amount
+    /// logged for it per day. Weeks start on `week_start`.
+    pub fn compute(
+        habit: &Habit,
+        amount_per_day: &HashMap<NaiveDate, usize>,
+        today: NaiveDate,
+        week_start: &Day,
+    ) -> Self {
+        let Some(first_day) = amount_per_day.keys().min().copied() else {
+            return Self::default();
+        };
+        let expected_days =
+            habit.expected_days(amount_per_day, first_day, today, today, week_start);
+
+        let mut current: Option<Streak> = None;
+        let mut longest: Option<Streak> = None;
+        for day in first_day.iter_days().take_while(|day| *day <= today) {
+            if !expected_days.contains(&day) {
+                continue;
+            }
+
+            if habit.is_done_on(amount_per_day, &day) {
+                let streak = match current {
+                    Some(streak) => Streak {
+                        length: streak.length + 1,
+                        end: day,
+                        ..streak
+                    },
+                    None => Streak {
+                        length: 1,
+                        start: day,
+                        end: day,
+                    },
+                };
+                if longest.is_none_or(|longest| streak.length >= longest.length) {
+                    longest = Some(streak);
+                }
+                current = Some(streak);
+            } else if day < today {
+                current = None;
+            }
+        }
+
+        Streaks { current, longest }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::times::Times;
+    use std::str::FromStr;
+
+    fn habit(schedule: &str) -> Habit {
+        Habit::new(
+            String::from("run"),
+            String::from("Go for a run"),
+            Schedule::from_str(schedule).unwrap(),
+            Times::from_str("08:00").unwrap(),
+        )
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::from_str(s).unwrap()
+    }
+
+    fn amount_per_day(days: &[&str]) -> HashMap<NaiveDate, usize> {
+        days.iter().map(|day| (date(day), 1)).collect()
+    }
+
+    #[test]
+    fn unscheduled_days_do_not_break_streaks() {
+        // 2024-05-06 is a Monday, nothing is logged on Tuesday
+        let habit = habit("Monday,Wednesday,Friday");
+        let amount_per_day = amount_per_day(&["2024-05-06", "2024-05-08", "2024-05-10"]);
+        let streaks = Streaks::compute(&habit, &amount_per_day, date("2024-05-11"), &Day::Monday);
+
+        let streak = Streak {
+            length: 3,
+            start: date("2024-05-06"),
+            end: date("2024-05-10"),
+        };
+        assert_eq!(streaks.current, Some(streak));
+        assert_eq!(streaks.longest, Some(streak));
+    }
+
+    #[test]
+    fn unfinished_today_does_not_break_current_streak() {
+        let habit = habit("daily");
+        let amount_per_day = amount_per_day(&["2024-05-06", "2024-05-07"]);
+        let streaks = Streaks::compute(&habit, &amount_per_day, date("2024-05-08"), &Day::Monday);
+        assert_eq!(
+            streaks.current,
+            Some(Streak {
+                length: 2,
+                start: date("2024-05-06"),
+                end: date("2024-05-07"),
+            })
+        );
+
+        // until the day is over
+        let streaks = Streaks::compute(&habit, &amount_per_day, date("2024-05-09"), &Day::Monday);
+        assert_eq!(streaks.current, None);
+        assert_eq!(streaks.longest.map(|streak| streak.length), Some(2));
+    }
+
+    #[test]
+    fn pauses_do_not_break_streaks() {
+        let mut habit = habit("daily");
+        habit.pause(date("2024-05-09"), date("2024-05-10"));
+        let amount_per_day = amount_per_day(&[
+            "2024-05-06",
+            "2024-05-07",
+            "2024-05-08",
+            "2024-05-11",
+            "2024-05-12",
+        ]);
+        let streaks = Streaks::compute(&habit, &amount_per_day, date("2024-05-14"), &Day::Monday);
+
+        assert_eq!(streaks.current, None);
+        assert_eq!(
+            streaks.longest,
+            Some(Streak {
+                length: 5,
+                start: date("2024-05-06"),
+                end: date("2024-05-12"),
+            })
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Manage the tags of habits.
+///
+/// Tags group habits (e.g. health, work, learning), and can be used
+/// to filter habits with --tag in 'habit list', 'habit today', 'habit stats',
+/// 'habit show' and 'habit export'.
+pub struct TagCli {
+    #[command(subcommand)]
+    pub command: TagCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum TagCommand {
+    /// Add tags to a habit.
+    Add {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the habit to tag.
+        habit: String,
+
+        #[clap(verbatim_doc_comment)]
+        #[clap(required = true, value_parser = parse_tag)]
+        /// The tags to add.
+        tags: Vec<String>,
+    },
+    /// Remove tags from a habit.
+    Remove {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the habit to untag.
+        habit: String,
+
+        #[clap(verbatim_doc_comment)]
+        #[clap(required = true, value_parser = parse_tag)]
+        /// The tags to remove.
+        tags: Vec<String>,
+    },
+    /// List the tags in use, with their number of habits.
+    List,
+}
+
+/// Tags cannot be empty, nor contain commas since they are joined with those.
+fn parse_tag(s: &str) -> Result<String, String> {
+    let tag = s.trim();
+    if tag.is_empty() {
+        Err(String::from("Tag cannot be empty."))
+    } else if tag.contains(',') {
+        Err(String::from("Tag cannot contain commas."))
+    } else {
+        Ok(tag.to_string())
+    }
+}
@@ -0,0 +1,84 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::tag::cli::{TagCli, TagCommand};
+use anyhow::anyhow;
+use colored::Colorize;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: TagCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(TagEngine {
+        db_path,
+        command: cli.command,
+    })
+}
+
+struct TagEngine {
+    db_path: PathBuf,
+    command: TagCommand,
+}
+
+impl Engine for TagEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        match self.command {
+            TagCommand::Add {
+                ref habit,
+                ref tags,
+            } => {
+                if !db::habit_exists(&conn, habit)? {
+                    return Err(anyhow!("Habit '{}' does not exists!", habit));
+                }
+                for tag in tags {
+                    if db::tag_insert(&conn, habit, tag)? {
+                        println!("Tag '{}' successfully added to habit '{}'!", tag, habit);
+                    } else {
+                        println!(
+                            "{} Habit '{}' already has tag '{}'.",
+                            "Warning:".yellow().bold(),
+                            habit,
+                            tag
+                        );
+                    }
+                }
+                Ok(())
+            }
+            TagCommand::Remove {
+                ref habit,
+                ref tags,
+            } => {
+                if !db::habit_exists(&conn, habit)? {
+                    return Err(anyhow!("Habit '{}' does not exists!", habit));
+                }
+                for tag in tags {
+                    if db::tag_delete(&conn, habit, tag)? {
+                        println!("Tag '{}' successfully removed from habit '{}'!", tag, habit);
+                    } else {
+                        println!(
+                            "{} Habit '{}' does not have tag '{}'.",
+                            "Warning:".yellow().bold(),
+                            habit,
+                            tag
+                        );
+                    }
+                }
+                Ok(())
+            }
+            TagCommand::List => {
+                for (tag, n_habits) in db::tag_get_all(&conn)? {
+                    println!(
+                        "{} {}",
+                        tag,
+                        format!(
+                            "({} {})",
+                            n_habits,
+                            if n_habits <= 1 { "habit" } else { "habits" }
+                        )
+                        .bright_black()
+                    );
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod cli;
+mod cron;
+mod engine;
+mod systemd;
+
+pub use cli::TimersCli;
+pub use engine::get_engine;
@@ -0,0 +1,50 @@
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Manage systemd user timers or crontab entries reminding of habits.
+///
+/// Timers are set at the times of each habit on the days it is scheduled on,
+/// and run 'habit remind --check <habit>' (see 'habit remind').
+/// Habits not scheduled on fixed days of the week get timers every day,
+/// 'habit remind' only reminding of them on the days they are due.
+/// Timers of different profiles or databases are kept apart.
+pub struct TimersCli {
+    #[command(subcommand)]
+    pub command: TimersCommand,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum TimersCommand {
+    /// Install timers for habits, replacing those installed before.
+    Install {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the habit to install timers for.
+        ///
+        /// Defaults to all habits of the profile or database in use.
+        habit: Option<String>,
+
+        #[clap(verbatim_doc_comment)]
+        #[clap(long, action)]
+        /// Install crontab entries instead of systemd user timers.
+        cron: bool,
+
+        #[clap(verbatim_doc_comment)]
+        #[clap(long, short, action)]
+        /// Only print the timers, without installing them.
+        print: bool,
+    },
+    /// Uninstall the timers of habits.
+    Uninstall {
+        #[clap(verbatim_doc_comment)]
+        /// The name of the habit to uninstall timers for.
+        ///
+        /// Defaults to all habits of the profile or database in use.
+        habit: Option<String>,
+
+        #[clap(verbatim_doc_comment)]
+        #[clap(long, action)]
+        /// Uninstall crontab entries instead of systemd user timers.
+        cron: bool,
+    },
+}
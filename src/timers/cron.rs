@@ -0,0 +1,283 @@
+use crate::habit::Habit;
+use crate::timers::engine::{self, Remind};
+use anyhow::{anyhow, Context};
+use chrono::Weekday;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Start of the comment line preceding each crontab entry made by habit,
+/// to only ever uninstall those. It is followed by the id of the database
+/// and the name of the habit.
+const MARKER: &str = "# habit reminder for ";
+
+pub fn install(
+    habits: &[Habit],
+    remind: &Remind,
+    replace_all: bool,
+    print: bool,
+) -> anyhow::Result<()> {
+    let db_id = remind.db_id();
+    let mut lines = Vec::new();
+    for habit in habits {
+        lines.extend(entries(habit, remind, &db_id));
+    }
+
+    if print {
+        for line in lines {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    let mut crontab = if replace_all {
+        without_entries(&read_crontab()?, &db_id, None)
+    } else {
+        let mut crontab = read_crontab()?;
+        for habit in habits {
+            crontab = without_entries(&crontab, &db_id, Some(&habit.name));
+        }
+        crontab
+    };
+    crontab.extend(lines);
+    write_crontab(&crontab)?;
+    for habit in habits {
+        println!(
+            "Crontab entries of habit '{}' successfully installed!",
+            habit.name
+        );
+    }
+
+    Ok(())
+}
+
+/// Uninstall the crontab entries of `habit`, or of all habits,
+/// of the database with id `db_id`.
+pub fn uninstall(db_id: &str, habit: Option<&str>) -> anyhow::Result<()> {
+    let crontab = read_crontab()?;
+    let new_crontab = without_entries(&crontab, db_id, habit);
+    if new_crontab.len() == crontab.len() {
+        return Ok(());
+    }
+    write_crontab(&new_crontab)?;
+    match habit {
+        Some(habit) => println!(
+            "Crontab entries of habit '{}' successfully uninstalled!",
+            habit
+        ),
+        None => println!("Crontab entries of all habits successfully uninstalled!"),
+    }
+
+    Ok(())
+}
+
+/// The crontab entries of `habit` of the database with id `db_id`,
+/// each preceded by a marker comment.
+fn entries(habit: &Habit, remind: &Remind, db_id: &str) -> Vec<String> {
+    let command = remind
+        .args(&habit.name)
+        .iter()
+        .map(|arg| quote(arg))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut lines = Vec::new();
+    for (days, at) in engine::calendar(habit) {
+        let days = if days.len() == 7 {
+            String::from("*")
+        } else {
+            days.into_iter()
+                .map(|day| Weekday::from(day).num_days_from_sunday().to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        lines.push(marker(db_id, &habit.name));
+        lines.push(format!(
+            "{} {} * * {} {}",
+            at.minutes, at.hour, days, command
+        ));
+    }
+
+    lines
+}
+
+/// The start of the marker comments of the habits of the database with id `db_id`.
+fn marker_prefix(db_id: &str) -> String {
+    format!("{}{} ", MARKER, db_id)
+}
+
+fn marker(db_id: &str, habit: &str) -> String {
+    format!("{}'{}'", marker_prefix(db_id), habit)
+}
+
+/// The lines of `crontab` without the entries of `habit`, or of all habits,
+/// of the database with id `db_id`, nor their marker comments.
+fn without_entries(crontab: &[String], db_id: &str, habit: Option<&str>) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut skip_next = false;
+    for line in crontab {
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        let is_marker = match habit {
+            Some(habit) => *line == marker(db_id, habit),
+            None => line.starts_with(&marker_prefix(db_id)),
+        };
+        if is_marker {
+            skip_next = true;
+            continue;
+        }
+        lines.push(line.clone());
+    }
+
+    lines
+}
+
+/// Quote `arg` for the shell, escaping the percent signs crontab turns into newlines.
+fn quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''").replace('%', "\\%"))
+}
+
+/// The lines of the crontab of the user, empty if they have none.
+fn read_crontab() -> anyhow::Result<Vec<String>> {
+    let output = Command::new("crontab")
+        .arg("-l")
+        .output()
+        .with_context(|| "Failed to run crontab. Is cron installed?")?;
+    if !output.status.success() {
+        // crontab fails when there is no crontab yet, which is as good as an empty one
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no crontab for") {
+            return Ok(Vec::new());
+        }
+        return Err(anyhow!(
+            "Failed to read the crontab ({}): {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.to_string())
+        .collect())
+}
+
+fn write_crontab(lines: &[String]) -> anyhow::Result<()> {
+    let mut child = Command::new("crontab")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| "Failed to run crontab. Is cron installed?")?;
+    let mut content = lines.join("\n");
+    content.push('\n');
+    child
+        .stdin
+        .take()
+        .expect("The stdin of crontab should be piped.")
+        .write_all(content.as_bytes())
+        .with_context(|| "Failed to write the crontab.")?;
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(anyhow!("Failed to install the crontab ({}).", status));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::times::Times;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn remind() -> Remind {
+        Remind {
+            exe: PathBuf::from("/usr/bin/habit"),
+            db_path: PathBuf::from("/home/me/habit.db"),
+        }
+    }
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn entries_of_habit() {
+        let habit = Habit::new(
+            String::from("50% off"),
+            String::new(),
+            Schedule::from_str("Monday,Wednesday,Saturday").unwrap(),
+            Times::from_str("08:00,sat 10:30").unwrap(),
+        );
+        assert_eq!(
+            entries(&habit, &remind(), "0123abcd"),
+            lines(&[
+                "# habit reminder for 0123abcd '50% off'",
+                "0 8 * * 1,3 '/usr/bin/habit' '--db' '/home/me/habit.db' 'remind' '--check' '50\\% off'",
+                "# habit reminder for 0123abcd '50% off'",
+                "30 10 * * 6 '/usr/bin/habit' '--db' '/home/me/habit.db' 'remind' '--check' '50\\% off'",
+            ])
+        );
+
+        let habit = Habit::new(
+            String::from("run"),
+            String::new(),
+            Schedule::from_str("3 per week").unwrap(),
+            Times::from_str("07:05").unwrap(),
+        );
+        assert_eq!(
+            entries(&habit, &remind(), "0123abcd")[1],
+            "5 7 * * * '/usr/bin/habit' '--db' '/home/me/habit.db' 'remind' '--check' 'run'"
+        );
+    }
+
+    #[test]
+    fn quote_for_the_shell() {
+        assert_eq!(quote("run"), "'run'");
+        assert_eq!(quote("it's"), "'it'\\''s'");
+        assert_eq!(quote("100%"), "'100\\%'");
+    }
+
+    #[test]
+    fn without_entries_of_habits() {
+        let crontab = lines(&[
+            "MAILTO=me",
+            "# habit reminder for 0123abcd 'run'",
+            "0 8 * * * habit remind --check run",
+            "0 0 * * * backup",
+            "# habit reminder for 0123abcd 'read'",
+            "0 21 * * * habit remind --check read",
+            "# habit reminder for 4567cdef 'run'",
+            "0 9 * * * habit remind --check run",
+            "# habit reminder for 0123abcd 'running'",
+            "0 6 * * * habit remind --check running",
+        ]);
+
+        assert_eq!(
+            without_entries(&crontab, "0123abcd", Some("run")),
+            lines(&[
+                "MAILTO=me",
+                "0 0 * * * backup",
+                "# habit reminder for 0123abcd 'read'",
+                "0 21 * * * habit remind --check read",
+                "# habit reminder for 4567cdef 'run'",
+                "0 9 * * * habit remind --check run",
+                "# habit reminder for 0123abcd 'running'",
+                "0 6 * * * habit remind --check running",
+            ])
+        );
+        assert_eq!(
+            without_entries(&crontab, "0123abcd", None),
+            lines(&[
+                "MAILTO=me",
+                "0 0 * * * backup",
+                "# habit reminder for 4567cdef 'run'",
+                "0 9 * * * habit remind --check run",
+            ])
+        );
+        assert_eq!(without_entries(&crontab, "89abcdef", None), crontab);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::habit::{At, Day, Habit};
+use crate::schedule::Schedule;
+use crate::timers::cli::{TimersCli, TimersCommand};
+use crate::timers::{cron, systemd};
+use anyhow::Context;
+use chrono::Weekday;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn get_engine(cli: TimersCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(TimersEngine {
+        db_path,
+        command: cli.command,
+    })
+}
+
+struct TimersEngine {
+    db_path: PathBuf,
+    command: TimersCommand,
+}
+
+impl Engine for TimersEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        match self.command {
+            TimersCommand::Install {
+                ref habit,
+                cron,
+                print,
+            } => {
+                let conn = db::open_db(&self.db_path)?;
+                let all_habits = db::habit_get_all(&conn)?;
+                let habits = match habit {
+                    Some(habit_name) => vec![db::habit_get_by_name(&conn, habit_name)?],
+                    None => all_habits
+                        .iter()
+                        .filter(|habit| !habit.archived)
+                        .cloned()
+                        .collect(),
+                };
+                let remind = Remind {
+                    exe: env::current_exe()
+                        .with_context(|| "Failed to get the path of the habit executable.")?,
+                    db_path: self.db_path()?,
+                };
+
+                // installing for all habits also removes the timers of deleted habits
+                let replace_all = habit.is_none();
+                if cron {
+                    cron::install(&habits, &remind, replace_all, print)
+                } else {
+                    // checked against all habits, so that timers of others are not replaced
+                    systemd::check_unit_names(&all_habits, &remind.db_id())?;
+                    systemd::install(&habits, &remind, replace_all, print)
+                }
+            }
+            TimersCommand::Uninstall { ref habit, cron } => {
+                let db_id = db_id(&self.db_path()?);
+                if cron {
+                    cron::uninstall(&db_id, habit.as_deref())
+                } else {
+                    systemd::uninstall(&db_id, habit.as_deref())
+                }
+            }
+        }
+    }
+}
+
+impl TimersEngine {
+    /// The absolute path of the database, for timers not to depend on the directory
+    /// they run in.
+    fn db_path(&self) -> anyhow::Result<PathBuf> {
+        fs::canonicalize(&self.db_path).with_context(|| {
+            format!(
+                "Failed to get the absolute path of {}.",
+                self.db_path.to_string_lossy()
+            )
+        })
+    }
+}
+
+/// A short id of the database at `db_path`, put in the names of timers so that
+/// those of the habits of different databases (e.g. profiles) are kept apart.
+///
+/// This is the FNV-1a hash of the path, which unlike the hashers of the standard library
+/// is sure to stay the same across versions, for timers to be found again.
+pub fn db_id(db_path: &Path) -> String {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in db_path.to_string_lossy().bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    format!("{:08x}", hash)
+}
+
+/// The command run by timers to remind of habits.
+pub struct Remind {
+    pub exe: PathBuf,
+    /// The database of the habits, so that timers use it whatever the profile in use.
+    pub db_path: PathBuf,
+}
+
+impl Remind {
+    pub fn db_id(&self) -> String {
+        db_id(&self.db_path)
+    }
+
+    /// The program and arguments to remind of `habit`.
+    pub fn args(&self, habit: &str) -> Vec<String> {
+        vec![
+            self.exe.to_string_lossy().to_string(),
+            String::from("--db"),
+            self.db_path.to_string_lossy().to_string(),
+            String::from("remind"),
+            String::from("--check"),
+            habit.to_string(),
+        ]
+    }
+}
+
+/// The times to set timers at for `habit`, each with the days of the week it is for.
+pub fn calendar(habit: &Habit) -> Vec<(Vec<Day>, At)> {
+    let mut calendar: Vec<(Vec<Day>, At)> = Vec::new();
+    for day in (0..7).map(|i| Day::from(Weekday::try_from(i).expect("Weekdays go from 0 to 6."))) {
+        if let Schedule::Days(ref days) = habit.schedule {
+            if !days.contains(&day) {
+                continue;
+            }
+        }
+        for at in habit.at.on_day(&day) {
+            match calendar.iter_mut().find(|(_, other)| *other == at) {
+                Some((days, _)) => days.push(day.clone()),
+                None => calendar.push((vec![day.clone()], at)),
+            }
+        }
+    }
+    calendar.sort_by_key(|(_, at)| at.clone());
+
+    calendar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::times::Times;
+    use std::str::FromStr;
+
+    #[test]
+    fn db_ids_are_stable() {
+        assert_eq!(db_id(Path::new("")), "811c9dc5");
+        assert_eq!(db_id(Path::new("/home/me/habit.db")), "21c35597");
+        assert_ne!(
+            db_id(Path::new("/home/me/habit.db")),
+            db_id(Path::new("/home/me/work.db"))
+        );
+    }
+
+    #[test]
+    fn calendar_groups_days_by_time() {
+        let at = |s: &str| At::from_str(s).unwrap();
+        let habit = Habit::new(
+            String::from("run"),
+            String::new(),
+            Schedule::from_str("Monday,Wednesday,Saturday").unwrap(),
+            Times::from_str("08:00,sat 10:30").unwrap(),
+        );
+        assert_eq!(
+            calendar(&habit),
+            vec![
+                (vec![Day::Monday, Day::Wednesday], at("08:00")),
+                (vec![Day::Saturday], at("10:30")),
+            ]
+        );
+
+        let habit = Habit::new(
+            String::from("run"),
+            String::new(),
+            Schedule::from_str("3 per week").unwrap(),
+            Times::from_str("07:05,19:00").unwrap(),
+        );
+        let all_days: Vec<Day> = (0..7)
+            .map(|i| Day::from(Weekday::try_from(i).unwrap()))
+            .collect();
+        assert_eq!(
+            calendar(&habit),
+            vec![(all_days.clone(), at("07:05")), (all_days, at("19:00"))]
+        );
+    }
+}
@@ -0,0 +1,333 @@
+use crate::habit::{Day, Habit};
+use crate::timers::engine::{self, Remind};
+use anyhow::{anyhow, Context};
+use directories::BaseDirs;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// First line of the unit files made by habit, to only ever uninstall those.
+const HEADER: &str = "# Generated by 'habit schedule install', do not edit.";
+
+pub fn install(
+    habits: &[Habit],
+    remind: &Remind,
+    replace_all: bool,
+    print: bool,
+) -> anyhow::Result<()> {
+    let dir = units_dir()?;
+    let db_id = remind.db_id();
+
+    if print {
+        for habit in habits {
+            let name = unit_name(&db_id, &habit.name);
+            println!(
+                "# {}",
+                dir.join(format!("{}.service", name)).to_string_lossy()
+            );
+            println!("{}", service(habit, remind));
+            println!(
+                "# {}",
+                dir.join(format!("{}.timer", name)).to_string_lossy()
+            );
+            println!("{}", timer(habit));
+        }
+        return Ok(());
+    }
+
+    if replace_all {
+        uninstall(&db_id, None)?;
+    }
+    fs::create_dir_all(&dir)?;
+    for habit in habits {
+        let name = unit_name(&db_id, &habit.name);
+        for (path, content) in [
+            (
+                dir.join(format!("{}.service", name)),
+                service(habit, remind),
+            ),
+            (dir.join(format!("{}.timer", name)), timer(habit)),
+        ] {
+            fs::write(&path, content).with_context(|| {
+                format!("Failed to write unit file {}.", path.to_string_lossy())
+            })?;
+        }
+    }
+    systemctl(&["daemon-reload"])?;
+    for habit in habits {
+        systemctl(&[
+            "enable",
+            "--now",
+            &format!("{}.timer", unit_name(&db_id, &habit.name)),
+        ])?;
+        println!("Timer of habit '{}' successfully installed!", habit.name);
+    }
+
+    Ok(())
+}
+
+/// Check that no two of `habits` of the database with id `db_id` share unit names,
+/// which would happen with names only differing by characters not allowed in those.
+pub fn check_unit_names(habits: &[Habit], db_id: &str) -> anyhow::Result<()> {
+    for (i, habit) in habits.iter().enumerate() {
+        let name = unit_name(db_id, &habit.name);
+        if let Some(other) = habits[..i]
+            .iter()
+            .find(|other| unit_name(db_id, &other.name) == name)
+        {
+            return Err(anyhow!(
+                "Habits '{}' and '{}' would have the same timer {}. Rename one of them with 'habit edit'.",
+                other.name,
+                habit.name,
+                name
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Uninstall the timers of `habit`, or of all habits, of the database with id `db_id`.
+pub fn uninstall(db_id: &str, habit: Option<&str>) -> anyhow::Result<()> {
+    let dir = units_dir()?;
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let mut timers = Vec::new();
+    for entry in fs::read_dir(&dir)
+        .with_context(|| format!("Failed to read directory {}.", dir.to_string_lossy()))?
+    {
+        let path = entry?.path();
+        let Some(file_name) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let Some(name) = file_name
+            .strip_suffix(".timer")
+            .or_else(|| file_name.strip_suffix(".service"))
+        else {
+            continue;
+        };
+        let is_of_habit = match habit {
+            Some(habit) => name == unit_name(db_id, habit),
+            None => name.starts_with(&unit_name_prefix(db_id)),
+        };
+        if !is_of_habit || !is_generated(&path) {
+            continue;
+        }
+        if file_name.ends_with(".timer") {
+            timers.push(name.to_string());
+        }
+    }
+
+    if timers.is_empty() {
+        return Ok(());
+    }
+    for name in timers {
+        systemctl(&["disable", "--now", &format!("{}.timer", name)])?;
+        for ext in ["timer", "service"] {
+            let path = dir.join(format!("{}.{}", name, ext));
+            if path.exists() {
+                fs::remove_file(&path).with_context(|| {
+                    format!("Failed to remove unit file {}.", path.to_string_lossy())
+                })?;
+            }
+        }
+        println!("Timer {} successfully uninstalled!", name);
+    }
+    systemctl(&["daemon-reload"])?;
+
+    Ok(())
+}
+
+fn units_dir() -> anyhow::Result<PathBuf> {
+    let base_dirs = BaseDirs::new().ok_or_else(|| anyhow!("Failed to find the home directory."))?;
+    Ok(base_dirs.config_dir().join("systemd").join("user"))
+}
+
+fn is_generated(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|content| content.starts_with(HEADER))
+}
+
+/// The start of the names of the units of the habits of the database with id `db_id`.
+fn unit_name_prefix(db_id: &str) -> String {
+    format!("habit-{}-", db_id)
+}
+
+/// The name of the units of `habit` of the database with id `db_id`,
+/// keeping only the characters allowed in unit names.
+fn unit_name(db_id: &str, habit: &str) -> String {
+    let name: String = habit
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}{}", unit_name_prefix(db_id), name)
+}
+
+fn service(habit: &Habit, remind: &Remind) -> String {
+    format!(
+        "{}
+[Unit]
+Description=Remind of habit '{}'
+
+[Service]
+Type=oneshot
+ExecStart={}
+",
+        HEADER,
+        habit.name,
+        remind
+            .args(&habit.name)
+            .iter()
+            .map(|arg| quote(arg))
+            .collect::<Vec<String>>()
+            .join(" ")
+    )
+}
+
+fn timer(habit: &Habit) -> String {
+    let on_calendar: String = engine::calendar(habit)
+        .iter()
+        .map(|(days, at)| {
+            let days = if days.len() == 7 {
+                String::from("")
+            } else {
+                days.iter()
+                    .map(|day| day_abbreviation(day))
+                    .collect::<Vec<&str>>()
+                    .join(",")
+                    + " "
+            };
+            format!("OnCalendar={}*-*-* {}:00\n", days, at)
+        })
+        .collect();
+
+    format!(
+        "{}
+[Unit]
+Description=Remind of habit '{}' at its times
+
+[Timer]
+{}
+[Install]
+WantedBy=timers.target
+",
+        HEADER, habit.name, on_calendar
+    )
+}
+
+fn day_abbreviation(day: &Day) -> &'static str {
+    match day {
+        Day::Monday => "Mon",
+        Day::Tuesday => "Tue",
+        Day::Wednesday => "Wed",
+        Day::Thursday => "Thu",
+        Day::Friday => "Fri",
+        Day::Saturday => "Sat",
+        Day::Sunday => "Sun",
+    }
+}
+
+/// Quote `arg` for a command line of a unit file, escaping specifiers and variables.
+fn quote(arg: &str) -> String {
+    format!(
+        "\"{}\"",
+        arg.replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%")
+            .replace('$', "$$")
+    )
+}
+
+fn systemctl(args: &[&str]) -> anyhow::Result<()> {
+    let status = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .status()
+        .with_context(|| {
+            "Failed to run systemctl. Use --cron to install crontab entries instead."
+        })?;
+    if !status.success() {
+        return Err(anyhow!(
+            "'systemctl --user {}' failed ({}).",
+            args.join(" "),
+            status
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::times::Times;
+    use std::str::FromStr;
+
+    fn new_habit(name: &str, schedule: &str, at: &str) -> Habit {
+        Habit::new(
+            String::from(name),
+            String::new(),
+            Schedule::from_str(schedule).unwrap(),
+            Times::from_str(at).unwrap(),
+        )
+    }
+
+    #[test]
+    fn unit_names() {
+        assert_eq!(unit_name("0123abcd", "run"), "habit-0123abcd-run");
+        assert_eq!(
+            unit_name("0123abcd", "read 20 pages/day"),
+            "habit-0123abcd-read_20_pages_day"
+        );
+        assert!(unit_name("0123abcd", "run").starts_with(&unit_name_prefix("0123abcd")));
+    }
+
+    #[test]
+    fn same_unit_names_are_rejected() {
+        let habits = [
+            new_habit("read", "daily", "21:00"),
+            new_habit("go out", "daily", "18:00"),
+            new_habit("go-out", "daily", "18:00"),
+        ];
+        assert!(check_unit_names(&habits[..2], "0123abcd").is_ok());
+        assert!(check_unit_names(&habits[1..], "0123abcd").is_ok());
+
+        let habits = [
+            new_habit("go out", "daily", "18:00"),
+            new_habit("go/out", "daily", "18:00"),
+        ];
+        assert!(check_unit_names(&habits, "0123abcd").is_err());
+    }
+
+    #[test]
+    fn calendar_of_timer() {
+        let habit = new_habit("run", "Monday,Wednesday,Saturday", "08:00,sat 10:30");
+        assert!(timer(&habit).starts_with(HEADER));
+        assert!(timer(&habit).contains(
+            "[Timer]\nOnCalendar=Mon,Wed *-*-* 08:00:00\nOnCalendar=Sat *-*-* 10:30:00\n\n"
+        ));
+
+        let habit = new_habit("run", "3 per week", "07:05");
+        assert!(timer(&habit).contains("[Timer]\nOnCalendar=*-*-* 07:05:00\n\n"));
+    }
+
+    #[test]
+    fn quote_for_unit_files() {
+        assert_eq!(quote("run"), "\"run\"");
+        assert_eq!(
+            quote("say \"100%\" for $5 \\o/"),
+            "\"say \\\"100%%\\\" for $$5 \\\\o/\""
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::habit::{At, Day, ParseAtError, ParseDayError};
+use crate::utils;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+/// A time of day to perform a habit at, every day or only on a day of the week.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeSlot {
+    /// The day of the week the time is for, `None` if it is for every day.
+    pub day: Option<Day>,
+    pub at: At,
+}
+
+impl fmt::Display for TimeSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.day {
+            Some(ref day) => write!(f, "{} {}", day, self.at),
+            None => write!(f, "{}", self.at),
+        }
+    }
+}
+
+/// The times of day to perform a habit at, each of them being a rep expected that day.
+///
+/// Times for a day of the week replace the times for every day on that day,
+/// e.g. '07:00,sat 10:00' is at 07:00, except on Saturdays where it is at 10:00.
+/// There is always at least one time for every day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Times(Vec<TimeSlot>);
+
+impl Times {
+    /// A single time, every day.
+    pub fn new(at: At) -> Self {
+        Self(vec![TimeSlot { day: None, at }])
+    }
+
+    /// All the times, those for every day first, then those for each day of the week.
+    pub fn slots(&self) -> &[TimeSlot] {
+        &self.0
+    }
+
+    /// The times to perform the habit at on `date`, in order.
+    pub fn on(&self, date: &NaiveDate) -> Vec<At> {
+        self.on_day(&Day::from(date.weekday()))
+    }
+
+    /// The times to perform the habit at on days of the week `day`, in order.
+    pub fn on_day(&self, day: &Day) -> Vec<At> {
+        let on_day: Vec<At> = self
+            .0
+            .iter()
+            .filter(|slot| slot.day.as_ref() == Some(day))
+            .map(|slot| slot.at.clone())
+            .collect();
+        if !on_day.is_empty() {
+            return on_day;
+        }
+
+        self.0
+            .iter()
+            .filter(|slot| slot.day.is_none())
+            .map(|slot| slot.at.clone())
+            .collect()
+    }
+
+    /// Describe the times in plain words, e.g. "at 07:00 and 19:00, at 10:00 on Saturdays".
+    pub fn describe(&self) -> String {
+        let display_ats = |slots: Vec<&TimeSlot>| -> String {
+            utils::display_list(
+                &slots
+                    .iter()
+                    .map(|slot| slot.at.to_string())
+                    .collect::<Vec<String>>(),
+            )
+        };
+
+        let mut parts = vec![format!(
+            "at {}",
+            display_ats(self.0.iter().filter(|slot| slot.day.is_none()).collect())
+        )];
+        let mut days: Vec<&Day> = self.0.iter().filter_map(|slot| slot.day.as_ref()).collect();
+        days.dedup();
+        for day in days {
+            parts.push(format!(
+                "at {} on {}s",
+                display_ats(
+                    self.0
+                        .iter()
+                        .filter(|slot| slot.day.as_ref() == Some(day))
+                        .collect()
+                ),
+                day
+            ));
+        }
+
+        parts.join(", ")
+    }
+}
+
+/// The form the times are input in and stored as, e.g. '08:00,20:00,Saturday 10:00'.
+impl fmt::Display for Times {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|slot| slot.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTimesError {
+    InvalidAt(ParseAtError),
+    InvalidDay(ParseDayError),
+    WrongFormat,
+    NoTimeForEveryDay,
+}
+
+impl fmt::Display for ParseTimesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAt(e) => write!(f, "{}", e),
+            Self::InvalidDay(e) => write!(f, "{}", e),
+            Self::WrongFormat => write!(
+                f,
+                "Wrong format. Should be times (hh:mm) separated by commas, each optionally for a day like 'sat 10:00'."
+            ),
+            Self::NoTimeForEveryDay => write!(
+                f,
+                "At least one time must be for every day, e.g. '07:00,sat 10:00'."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseTimesError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidAt(e) => Some(e),
+            Self::InvalidDay(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    fn description(&self) -> &str {
+        "description() is deprecated; use Display"
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        self.source()
+    }
+}
+
+/// Parses times as displayed: times (hh:mm) separated by commas, each optionally
+/// preceded by a day of the week (see Day::from_str), e.g. '08:00,20:00,sat 10:00'.
+/// The times are kept in order, without duplicates.
+impl FromStr for Times {
+    type Err = ParseTimesError;
+
+    fn from_str(s: &str) -> Result<Self, ParseTimesError> {
+        let mut slots = Vec::new();
+        for slot_str in s.split(',').map(|slot| slot.trim()) {
+            let slot = match slot_str.split_whitespace().collect::<Vec<&str>>()[..] {
+                [at] => TimeSlot {
+                    day: None,
+                    at: At::from_str(at).map_err(ParseTimesError::InvalidAt)?,
+                },
+                [day, at] => TimeSlot {
+                    day: Some(Day::from_str(day).map_err(ParseTimesError::InvalidDay)?),
+                    at: At::from_str(at).map_err(ParseTimesError::InvalidAt)?,
+                },
+                _ => return Err(ParseTimesError::WrongFormat),
+            };
+            slots.push(slot);
+        }
+        slots.sort();
+        slots.dedup();
+
+        if slots.first().is_none_or(|slot| slot.day.is_some()) {
+            return Err(ParseTimesError::NoTimeForEveryDay);
+        }
+
+        Ok(Times(slots))
+    }
+}
+
+// Serialized as a string, like it is input.
+// Habits used to have a single time, which is read the same way.
+impl Serialize for Times {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Times {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Times::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,21 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Show the habits to perform today.
+///
+/// Each habit is marked as done, pending or overdue (its time has passed).
+/// Exits with code 2 if there is still a habit to perform today, 0 otherwise,
+/// or 1 on errors.
+pub struct TodayCli {
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Show the habits to perform each day of the current week instead.
+    pub week: bool,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long = "tag", value_name = "TAG")]
+    /// Only show habits with this tag.
+    /// Can be given several times, for habits with any of the tags.
+    pub tags: Vec<String>,
+}
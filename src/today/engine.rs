@@ -0,0 +1,234 @@
+use crate::config::Config;
+use crate::db;
+use crate::engine::Engine;
+use crate::habit::{Day, Habit};
+use crate::schedule::Schedule;
+use crate::today::cli::TodayCli;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate};
+use colored::{ColoredString, Colorize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: TodayCli, db_path: PathBuf, config: Config) -> Box<dyn Engine> {
+    Box::new(TodayEngine {
+        db_path,
+        config,
+        week: cli.week,
+        tags: cli.tags,
+        anything_left_today: false,
+    })
+}
+
+struct TodayEngine {
+    db_path: PathBuf,
+    config: Config,
+    week: bool,
+    tags: Vec<String>,
+    /// Whether there is still a habit to perform today, once run.
+    anything_left_today: bool,
+}
+
+/// The status of a habit at one of its times on a given day it is scheduled on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// The target was reached for that time (a single rep for habits that are not measured),
+    /// the times of a day being done in order.
+    Done,
+    /// The target was not reached for that time, and the day is over.
+    Missed,
+    /// The target is not reached yet for that time today, and the time has passed.
+    Overdue,
+    /// The target is not reached yet for that time today, but there is still time.
+    Pending,
+    /// The target is not reached yet for that time today, but the habit can as well be done
+    /// on the days left this week or month (for habits to do a number of times per period).
+    Open,
+    /// The day is yet to come.
+    Upcoming,
+}
+
+impl Status {
+    /// The status of `habit` at the `i`-th of its times on `day`,
+    /// `None` if there is nothing to tell about it then.
+    fn of(
+        habit: &Habit,
+        day: NaiveDate,
+        i: usize,
+        amount_per_day: &HashMap<NaiveDate, usize>,
+        now: &DateTime<Local>,
+        week_start: &Day,
+    ) -> Option<Self> {
+        let today = now.date_naive();
+        if !habit.is_due_on(&day) {
+            return None;
+        }
+        let amount = amount_per_day.get(&day).copied().unwrap_or(0);
+        if i < habit.n_times_done_with(amount, &day) {
+            return Some(Status::Done);
+        }
+        let time = habit.at.on(&day)[i].time();
+
+        let Some((period, n_done)) = habit.period_progress(amount_per_day, &day, week_start) else {
+            return Some(if day < today {
+                Status::Missed
+            } else if day > today {
+                Status::Upcoming
+            } else if now.time() >= time {
+                Status::Overdue
+            } else {
+                Status::Pending
+            });
+        };
+
+        let n_left = (period.n as usize).saturating_sub(n_done);
+        if n_left == 0 || day > today {
+            None
+        } else if day < today {
+            habit
+                .expected_days(amount_per_day, day, day, today, week_start)
+                .contains(&day)
+                .then_some(Status::Missed)
+        } else if (n_left as i64) < (period.last - today).num_days() + 1 {
+            Some(Status::Open)
+        } else if now.time() >= time {
+            Some(Status::Overdue)
+        } else {
+            Some(Status::Pending)
+        }
+    }
+
+    fn display(&self) -> ColoredString {
+        match self {
+            Status::Done => "[x] done".green(),
+            Status::Missed => "[-] missed".red(),
+            Status::Overdue => "[!] overdue".red().bold(),
+            Status::Pending => "[ ] pending".yellow(),
+            Status::Open => "[ ] open".normal(),
+            Status::Upcoming => "[ ] upcoming".bright_black(),
+        }
+    }
+}
+
+impl Engine for TodayEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // paused habits are not due, so only archived ones need be left out
+        let habits: Vec<Habit> = db::habit_get_all(&conn)?
+            .into_iter()
+            .filter(|habit| !habit.archived && habit.matches_tags(&self.tags))
+            .collect();
+        let mut amount_per_day_per_habit: Vec<HashMap<NaiveDate, usize>> = Vec::new();
+        for habit in habits.iter() {
+            amount_per_day_per_habit
+                .push(db::get_all_amount_per_day_for_habit(&conn, &habit.name)?);
+        }
+
+        let now = Local::now();
+        let today = now.date_naive();
+        let days: Vec<NaiveDate> = if self.week {
+            let week_start = today
+                - Days::new(
+                    today
+                        .weekday()
+                        .days_since(self.config.week_start.clone().into())
+                        .into(),
+                );
+            week_start.iter_days().take(7).collect()
+        } else {
+            vec![today]
+        };
+
+        let name_width = habits.iter().map(|h| h.name.len()).max().unwrap_or(0);
+        for (i, day) in days.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            let title = format!(
+                "{} {}",
+                Day::from(day.weekday()),
+                day.format(&self.config.date_format)
+            );
+            if *day == today {
+                println!("{} {}", title.bold(), "(today)".bold());
+            } else {
+                println!("{}", title.bold());
+            }
+
+            // a row per time of each habit, in order of time
+            let mut rows = Vec::new();
+            for (habit, amount_per_day) in habits.iter().zip(amount_per_day_per_habit.iter()) {
+                let times = habit.at.on(day);
+                for (i, at) in times.iter().enumerate() {
+                    let Some(status) = Status::of(
+                        habit,
+                        *day,
+                        i,
+                        amount_per_day,
+                        &now,
+                        &self.config.week_start,
+                    ) else {
+                        continue;
+                    };
+                    if matches!(status, Status::Overdue | Status::Pending) {
+                        self.anything_left_today = true;
+                    }
+
+                    let mut progress = Vec::new();
+                    if (habit.is_measured() || times.len() > 1) && status != Status::Upcoming {
+                        progress.push(format!(
+                            "{}/{}",
+                            amount_per_day.get(day).copied().unwrap_or(0),
+                            habit.display_amount(habit.target_on(day))
+                        ));
+                    }
+                    if let Some((period, n_done)) =
+                        habit.period_progress(amount_per_day, day, &self.config.week_start)
+                    {
+                        progress.push(format!(
+                            "{}/{} {}",
+                            n_done,
+                            period.n,
+                            match habit.schedule {
+                                Schedule::TimesPerMonth(_) => "this month",
+                                _ => "this week",
+                            }
+                        ));
+                    }
+                    rows.push((at.clone(), habit, status, progress));
+                }
+            }
+            rows.sort_by_key(|(at, _, _, _)| at.clone());
+
+            let anything_scheduled = !rows.is_empty();
+            for (at, habit, status, progress) in rows {
+                let progress = if progress.is_empty() {
+                    "".normal()
+                } else {
+                    format!(" ({})", progress.join(", ")).bright_black()
+                };
+                println!(
+                    "    {}  {:<name_width$}  {}{}",
+                    at,
+                    habit.name,
+                    status.display(),
+                    progress
+                );
+            }
+            if !anything_scheduled {
+                println!("    {}", "Nothing scheduled.".bright_black());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn exit_code(&self) -> u8 {
+        // not 1, which is the exit code of errors
+        if self.anything_left_today {
+            2
+        } else {
+            0
+        }
+    }
+}
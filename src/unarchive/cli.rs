@@ -0,0 +1,10 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Track an archived habit again.
+pub struct UnarchiveCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit to unarchive.
+    pub habit: String,
+}
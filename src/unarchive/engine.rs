@@ -0,0 +1,35 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::unarchive::cli::UnarchiveCli;
+use anyhow::anyhow;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: UnarchiveCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(UnarchiveEngine {
+        db_path,
+        habit: cli.habit,
+    })
+}
+
+struct UnarchiveEngine {
+    db_path: PathBuf,
+    habit: String,
+}
+
+impl Engine for UnarchiveEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // errors if the habit does not exist
+        let habit = db::habit_get_by_name(&conn, &self.habit)?;
+        if !habit.archived {
+            return Err(anyhow!("Habit '{}' is not archived!", self.habit));
+        }
+
+        db::habit_update_archived(&conn, &self.habit, false)?;
+
+        println!("Habit '{}' successfully unarchived!", self.habit);
+
+        Ok(())
+    }
+}
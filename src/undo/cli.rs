@@ -0,0 +1,17 @@
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Delete the last logged rep, whatever date it was logged for.
+pub struct UndoCli {
+    #[clap(verbatim_doc_comment)]
+    /// The name of the habit for which to delete the last logged rep.
+    ///
+    /// Defaults to any habit.
+    pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Do not ask for confirmation.
+    pub yes: bool,
+}
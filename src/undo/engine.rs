@@ -0,0 +1,50 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::prompt;
+use crate::undo::cli::UndoCli;
+use anyhow::anyhow;
+use std::path::PathBuf;
+
+pub fn get_engine(cli: UndoCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(UndoEngine {
+        db_path,
+        habit: cli.habit,
+        yes: cli.yes,
+    })
+}
+
+struct UndoEngine {
+    db_path: PathBuf,
+    habit: Option<String>,
+    yes: bool,
+}
+
+impl Engine for UndoEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // check if habit exists in db, if not error
+        if let Some(ref habit) = self.habit {
+            if !db::habit_exists(&conn, habit)? {
+                return Err(anyhow!("Habit '{}' does not exists!", habit));
+            }
+        }
+
+        // find the last logged rep, whatever date it was logged for
+        let log = db::log_get_last_inserted(&conn, self.habit.as_deref())?
+            .ok_or_else(|| anyhow!("No rep to undo."))?;
+
+        // ask for confirmation
+        let confirmed = self.yes || prompt::ask_for_confirmation(&format!("Delete {}?", log))?;
+
+        // delete log
+        if confirmed {
+            db::log_delete(&conn, log.id)?;
+            println!("Log {} successfully deleted!", log.id);
+        } else {
+            println!("Nothing done.");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use chrono::NaiveDate;
+use clap::Args;
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+#[clap(verbatim_doc_comment)]
+/// Delete a logged rep.
+///
+/// Without --id or --date, pick the rep to delete among the most recent ones.
+pub struct UnlogCli {
+    #[clap(verbatim_doc_comment)]
+    #[clap(required_unless_present = "id")]
+    /// The name of the habit for which to delete a rep.
+    pub habit: Option<String>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, conflicts_with = "date")]
+    /// The id of the log to delete, as shown by 'habit log'.
+    pub id: Option<i64>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short)]
+    /// The date (YYYY-MM-DD) of the rep to delete.
+    ///
+    /// If there are several reps that day, pick the one to delete.
+    pub date: Option<NaiveDate>,
+
+    #[clap(verbatim_doc_comment)]
+    #[clap(long, short, action)]
+    /// Do not ask for confirmation.
+    pub yes: bool,
+}
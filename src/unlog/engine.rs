@@ -0,0 +1,82 @@
+use crate::db;
+use crate::engine::Engine;
+use crate::prompt;
+use crate::unlog::cli::UnlogCli;
+use anyhow::anyhow;
+use chrono::NaiveDate;
+use std::path::PathBuf;
+
+/// Number of recent logs to pick from when neither a log id nor a date is given.
+const N_RECENT_LOGS: usize = 10;
+
+pub fn get_engine(cli: UnlogCli, db_path: PathBuf) -> Box<dyn Engine> {
+    Box::new(UnlogEngine {
+        db_path,
+        habit: cli.habit,
+        id: cli.id,
+        date: cli.date,
+        yes: cli.yes,
+    })
+}
+
+struct UnlogEngine {
+    db_path: PathBuf,
+    habit: Option<String>,
+    id: Option<i64>,
+    date: Option<NaiveDate>,
+    yes: bool,
+}
+
+impl Engine for UnlogEngine {
+    fn run(&mut self) -> anyhow::Result<()> {
+        let conn = db::open_db(&self.db_path)?;
+
+        // find the log to delete
+        let log = match (self.id, &self.habit) {
+            (Some(id), habit) => {
+                let log = db::log_get_by_id(&conn, id)?;
+                if let Some(habit) = habit {
+                    if log.habit != *habit {
+                        return Err(anyhow!("Log {} is not for habit '{}'!", id, habit));
+                    }
+                }
+                log
+            }
+            (None, Some(habit)) => {
+                // check if habit exists in db, if not error
+                if !db::habit_exists(&conn, habit)? {
+                    return Err(anyhow!("Habit '{}' does not exists!", habit));
+                }
+
+                let logs = match self.date {
+                    Some(date) => db::get_logs_for_habit(&conn, habit)?
+                        .into_iter()
+                        .rev()
+                        .filter(|log| log.datetime().date_naive() == date)
+                        .collect(),
+                    None => db::get_n_most_recent_logs_for_habit(&conn, habit, N_RECENT_LOGS)?,
+                };
+
+                match logs.len() {
+                    0 => return Err(anyhow!("No rep to delete for habit '{}'.", habit)),
+                    1 if self.date.is_some() => logs[0].clone(),
+                    _ => prompt::prompt_log(&logs)?,
+                }
+            }
+            (None, None) => return Err(anyhow!("Either a habit or a log id is required.")),
+        };
+
+        // ask for confirmation
+        let confirmed = self.yes || prompt::ask_for_confirmation(&format!("Delete {}?", log))?;
+
+        // delete log
+        if confirmed {
+            db::log_delete(&conn, log.id)?;
+            println!("Log {} successfully deleted!", log.id);
+        } else {
+            println!("Nothing done.");
+        }
+
+        Ok(())
+    }
+}
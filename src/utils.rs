@@ -1,6 +1,7 @@
-use chrono::{DateTime, Datelike, TimeZone, Utc};
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone, Utc};
 
-use crate::habit::Day;
+use crate::habit::{At, Day};
 
 pub fn left_pad(s: &str, c: char, n: usize) -> String {
     if n <= s.len() {
@@ -18,18 +19,31 @@ pub fn left_pad(s: &str, c: char, n: usize) -> String {
 }
 
 pub fn display_days(days: &[Day]) -> String {
-    if days.is_empty() {
+    display_list(
+        &days
+            .iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<String>>(),
+    )
+}
+
+/// Join `items` with commas, except for the last two joined with "and".
+pub fn display_list(items: &[String]) -> String {
+    if items.is_empty() {
         return String::from("");
     }
+    if items.len() == 1 {
+        return items[0].clone();
+    }
 
     let mut res = String::from("");
-    for day in days.iter().take(days.len() - 2) {
-        res.push_str(&day.to_string());
+    for item in items.iter().take(items.len() - 2) {
+        res.push_str(item);
         res.push_str(", ");
     }
-    res.push_str(&days[days.len() - 2].to_string());
+    res.push_str(&items[items.len() - 2]);
     res.push_str(" and ");
-    res.push_str(&days[days.len() - 1].to_string());
+    res.push_str(&items[items.len() - 1]);
 
     res
 }
@@ -41,3 +55,15 @@ pub fn nth_day_of_year(datetime: &DateTime<Utc>) -> u16 {
 
     (datetime.signed_duration_since(first_day_of_year).num_days() + 1) as u16
 }
+
+/// The local date and time of `date` at `at`.
+pub fn local_datetime(date: &NaiveDate, at: &At) -> anyhow::Result<DateTime<Local>> {
+    let naive = date
+        .and_hms_opt(at.hour.into(), at.minutes.into(), 0)
+        .ok_or_else(|| anyhow!("Invalid time {}.", at))?;
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| anyhow!("{} at {} does not exist in the local time zone.", date, at))
+}